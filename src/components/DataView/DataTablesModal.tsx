@@ -81,7 +81,7 @@ export const DataTablesModal: React.FC<DataTablesModalProps> = ({
     if (dataType === 'results') {
       if (pairName) {
         // Export single pair results
-        csvContent = 'Replication,P-Value,Effect Size,CI Lower,CI Upper,S-Value,Significant\n';
+        csvContent = 'Replication,P-Value,Effect Size,CI Lower,CI Upper,S-Value,T-Statistic,Degrees of Freedom,Significant\n';
         const pairResult = multiPairResults.pairs_results.find(p => p.pair_name === pairName);
         if (pairResult) {
           pairResult.individual_results.forEach((result, index) => {
@@ -92,6 +92,9 @@ export const DataTablesModal: React.FC<DataTablesModalProps> = ({
               result.confidence_interval[0].toFixed(6),
               result.confidence_interval[1].toFixed(6),
               result.s_value.toFixed(6),
+              // Empty outside the t-test modes
+              result.t_statistic?.toFixed(6) ?? '',
+              result.degrees_of_freedom?.toFixed(6) ?? '',
               result.significant ? 'TRUE' : 'FALSE'
             ].join(',') + '\n';
           });
@@ -99,7 +102,7 @@ export const DataTablesModal: React.FC<DataTablesModalProps> = ({
         filename = `${pairName.replace(/[^a-z0-9]/gi, '_').toLowerCase()}_detailed_results`;
       } else {
         // Export all results
-        csvContent = 'Pair Name,Replication,P-Value,Effect Size,CI Lower,CI Upper,S-Value,Significant\n';
+        csvContent = 'Pair Name,Replication,P-Value,Effect Size,CI Lower,CI Upper,S-Value,T-Statistic,Degrees of Freedom,Significant\n';
         multiPairResults.pairs_results.forEach((pairResult) => {
           pairResult.individual_results.forEach((result, index) => {
             csvContent += [
@@ -110,6 +113,9 @@ export const DataTablesModal: React.FC<DataTablesModalProps> = ({
               result.confidence_interval[0].toFixed(6),
               result.confidence_interval[1].toFixed(6),
               result.s_value.toFixed(6),
+              // Empty outside the t-test modes
+              result.t_statistic?.toFixed(6) ?? '',
+              result.degrees_of_freedom?.toFixed(6) ?? '',
               result.significant ? 'TRUE' : 'FALSE'
             ].join(',') + '\n';
           });
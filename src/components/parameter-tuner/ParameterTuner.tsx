@@ -250,6 +250,8 @@ export const ParameterTuner: React.FC<ParameterTunerProps> = ({
                 <MenuItem value="welch" sx={{ fontSize: '12px' }}>Welch's t-test</MenuItem>
                 <MenuItem value="pooled" sx={{ fontSize: '12px' }}>Pooled t-test</MenuItem>
                 <MenuItem value="mann_whitney" sx={{ fontSize: '12px' }}>Mann-Whitney U</MenuItem>
+                <MenuItem value="paired" sx={{ fontSize: '12px' }}>Paired t-test</MenuItem>
+                <MenuItem value="permutation" sx={{ fontSize: '12px' }}>Permutation test</MenuItem>
               </Select>
             </FormControl>
           </Box>
@@ -17,8 +17,48 @@ import {
   SignificanceResult,
   ThresholdSensitivity,
   SamplePair,
-  GlobalSimulationSettings
+  GlobalSimulationSettings,
+  SimulationParams,
+  AggregatedResults,
+  ValueHistogramBin,
+  SignificanceRule,
+  Distribution,
+  TestRecommendation,
+  EndpointAnalysis,
+  StatisticKind,
+  CombineMethod,
+  EffectSizeClass,
+  ReplicationResults,
+  AllocationPoint,
+  DFCalibration,
+  CalibrationPoint,
+  QuantileSummary,
+  PosteriorEffectSize,
+  SampleDataset,
+  TestComparison,
+  SimulationEvent,
+  TTestOptions,
+  RopeDecisions,
+  TestAgreement,
+  SizeCalibration,
+  CiMethod,
+  TestType,
+  Alternative,
+  EffectSizeMetric,
+  AlphaPowerPoint,
+  PairedAnalysis,
+  CIWidthPoint,
+  MultipleComparison,
+  BatchSeeding,
+  PowerGridCell,
+  UniformityTest,
+  HistogramBin,
+  SimulationErrorKind,
+  SampleSizePoint,
+  Contamination
 } from '../types/simulation.types';
+import { RandomSource, createRandom, createIndexedRandom, deriveSeed, recordDraws } from '../utils/random';
+import { TDigest } from '../utils/tdigest';
 
 // Production-ready statistical simulation engine using jStat library
 // Implements verified statistical tests with proper algorithms
@@ -26,128 +66,2523 @@ import {
 // @ts-ignore - jStat is a well-established library but lacks TypeScript definitions
 import * as jStat from 'jstat';
 
+// Failure with a kind callers can match on; the message reads the same as a plain Error's
+export class SimulationError extends Error {
+  constructor(readonly kind: SimulationErrorKind, message: string) {
+    super(message);
+    this.name = 'SimulationError';
+  }
+}
+
+// Resamples per bootstrap CI when the CI method does not set n_boot
+const DEFAULT_BOOTSTRAP_RESAMPLES = 2000;
+
+// Label shuffles per simulation for the permutation test when num_permutations is not set
+const DEFAULT_NUM_PERMUTATIONS = 1000;
+
 // Statistical utility functions using jStat
-class StatisticalUtils {
-  // Generate normal random variable using jStat
-  static normalRandom(mean: number = 0, std: number = 1): number {
-    return (jStat as any).normal.sample(mean, std);
+export class StatisticalUtils {
+  // Generate normal random variable (Box-Muller) from the given uniform source
+  static normalRandom(mean: number = 0, std: number = 1, random: RandomSource = Math.random): number {
+    const u1 = 1 - random(); // (0, 1] so the log stays finite
+    const u2 = random();
+    return mean + std * Math.sqrt(-2 * Math.log(u1)) * Math.cos(2 * Math.PI * u2);
+  }
+
+  // Inverse standard normal CDF (Acklam's rational approximation, relative error < 1.2e-9)
+  static inverseNormalCDF(p: number): number {
+    const a = [-3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+      1.383577518672690e+02, -3.066479806614716e+01, 2.506628277459239e+00];
+    const b = [-5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+      6.680131188771972e+01, -1.328068155288572e+01];
+    const c = [-7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+      -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00];
+    const d = [7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+      3.754408661907416e+00];
+    const p_low = 0.02425;
+
+    if (p < p_low || p > 1 - p_low) {
+      const q = Math.sqrt(-2 * Math.log(p < p_low ? p : 1 - p));
+      const x = (((((c[0] * q + c[1]) * q + c[2]) * q + c[3]) * q + c[4]) * q + c[5]) /
+        ((((d[0] * q + d[1]) * q + d[2]) * q + d[3]) * q + 1);
+      return p < p_low ? x : -x;
+    }
+
+    const q = p - 0.5;
+    const r = q * q;
+    return (((((a[0] * r + a[1]) * r + a[2]) * r + a[3]) * r + a[4]) * r + a[5]) * q /
+      (((((b[0] * r + b[1]) * r + b[2]) * r + b[3]) * r + b[4]) * r + 1);
+  }
+
+  // Bulk normal draws: fill a uniform buffer, then transform it in one pass through the
+  // inverse CDF (one uniform and no trig per value). `npm run bench` compares it with Box-Muller
+  static normalRandomBuffer(n: number, mean: number, std: number, random: RandomSource = Math.random): number[] {
+    const buffer = new Float64Array(n);
+    for (let i = 0; i < n; i++) {
+      buffer[i] = random();
+    }
+
+    const samples = new Array<number>(n);
+    for (let i = 0; i < n; i++) {
+      // Guard the open interval: a uniform of exactly 0 would map to -Infinity
+      samples[i] = mean + std * StatisticalUtils.inverseNormalCDF(buffer[i] || Number.MIN_VALUE);
+    }
+    return samples;
+  }
+
+  // Draw one group's sample from its configured population distribution
+  static generateSamples(
+    n: number,
+    mean: number,
+    std: number,
+    distribution: Distribution = { type: 'normal' },
+    random: RandomSource = Math.random,
+    buffered: boolean = false
+  ): number[] {
+    switch (distribution.type) {
+      case 'normal':
+        if (buffered) {
+          return StatisticalUtils.normalRandomBuffer(n, mean, std, random);
+        }
+        return Array.from({length: n}, () => StatisticalUtils.normalRandom(mean, std, random));
+      case 'mixture': {
+        // Pick a component by weight, then draw from its normal offset by the group mean
+        const { components } = distribution;
+        return Array.from({length: n}, () => {
+          let u = random();
+          let component = components[components.length - 1];
+          for (const c of components) {
+            if (u < c.weight) {
+              component = c;
+              break;
+            }
+            u -= c.weight;
+          }
+          return mean + StatisticalUtils.normalRandom(component.mean, component.std, random);
+        });
+      }
+      // The remaining shapes are standardized to the group mean and std, so the standardized
+      // effect stays (mean1 - mean2) / pooled std; all use monotone inverse-CDF transforms
+      case 'laplace': {
+        // Laplace(0, b) has variance 2 b^2, so b = std / sqrt(2)
+        const scale = std / Math.SQRT2;
+        return Array.from({length: n}, () => {
+          const u = random() - 0.5;
+          return mean - scale * Math.sign(u) * Math.log(1 - 2 * Math.abs(u));
+        });
+      }
+      case 'lognormal': {
+        // exp(sigma Z) has mean e^(sigma^2 / 2) and variance (e^(sigma^2) - 1) e^(sigma^2)
+        const { sigma } = distribution;
+        const raw_mean = Math.exp(sigma ** 2 / 2);
+        const raw_std = Math.sqrt((Math.exp(sigma ** 2) - 1) * Math.exp(sigma ** 2));
+        return Array.from({length: n}, () => {
+          const z = StatisticalUtils.inverseNormalCDF(random() || Number.MIN_VALUE);
+          return mean + std * (Math.exp(sigma * z) - raw_mean) / raw_std;
+        });
+      }
+      case 'exponential':
+        // Exp(1) has mean 1 and std 1
+        return Array.from({length: n}, () => mean + std * (-Math.log(1 - random()) - 1));
+      case 'uniform':
+        // U(-sqrt(3), sqrt(3)) has unit variance
+        return Array.from({length: n}, () => mean + std * Math.sqrt(3) * (2 * random() - 1));
+      case 'student_t': {
+        // t with df > 2 has variance df / (df - 2)
+        const { df } = distribution;
+        const scale = std * Math.sqrt((df - 2) / df);
+        return Array.from({length: n}, () =>
+          mean + scale * (jStat as any).studentt.inv(random() || Number.MIN_VALUE, df));
+      }
+    }
+  }
+
+  // Mean and SD of the population a group is actually sampled from. Every shape but the mixture
+  // is standardized to the group mean and std; a mixture sits at the group mean plus its weighted
+  // component mean, with the spread of its components (the group std is unused)
+  static populationMoments(
+    mean: number,
+    std: number,
+    distribution: Distribution = { type: 'normal' }
+  ): { mean: number; std: number } {
+    if (distribution.type !== 'mixture') return { mean, std };
+
+    const { components } = distribution;
+    const offset = components.reduce((sum, c) => sum + c.weight * c.mean, 0);
+    const second_moment = components.reduce((sum, c) => sum + c.weight * (c.std ** 2 + c.mean ** 2), 0);
+    return { mean: mean + offset, std: Math.sqrt(second_moment - offset ** 2) };
+  }
+
+  // Contaminate a drawn sample: each value independently, with probability fraction, moves
+  // scale times further from the group mean. On a normal sample this is the contaminated normal
+  // (1 - fraction) N(mean, std^2) + fraction N(mean, (scale std)^2)
+  static contaminateSamples(
+    samples: number[],
+    mean: number,
+    contamination: Contamination | undefined,
+    random: RandomSource = Math.random
+  ): number[] {
+    if (!contamination || contamination.fraction === 0) return samples;
+    const { fraction, scale } = contamination;
+    return samples.map(x => random() < fraction ? mean + scale * (x - mean) : x);
+  }
+
+  static validateContamination(contamination: Contamination | undefined, group: 1 | 2): void {
+    if (!contamination) return;
+    if (!(contamination.fraction >= 0 && contamination.fraction <= 1)) {
+      throw new SimulationError('distribution_error', `Group ${group} contamination fraction must be in [0, 1]`);
+    }
+    if (!(contamination.scale > 0)) {
+      throw new SimulationError('distribution_error', `Group ${group} contamination scale must be positive`);
+    }
+  }
+
+  // Clustered normal sample: each cluster shares a random offset carrying icc of the
+  // total variance, subjects add independent within-cluster noise for the remainder
+  static generateClusteredSamples(
+    n: number,
+    mean: number,
+    std: number,
+    cluster_size: number,
+    icc: number,
+    random: RandomSource = Math.random
+  ): number[] {
+    const between_std = Math.sqrt(icc) * std;
+    const within_std = Math.sqrt(1 - icc) * std;
+    const samples: number[] = [];
+
+    while (samples.length < n) {
+      const cluster_mean = StatisticalUtils.normalRandom(mean, between_std, random);
+      const members = Math.min(cluster_size, n - samples.length);
+      for (let i = 0; i < members; i++) {
+        samples.push(StatisticalUtils.normalRandom(cluster_mean, within_std, random));
+      }
+    }
+    return samples;
+  }
+
+  // Variance inflation from clustering: 1 + (m - 1) * icc
+  static designEffect(cluster_size: number, icc: number): number {
+    return 1 + (cluster_size - 1) * icc;
+  }
+
+  // Reject distribution settings that cannot be sampled
+  static validateDistribution(distribution: Distribution | undefined): void {
+    if (distribution?.type === 'lognormal' && !(distribution.sigma > 0)) {
+      throw new SimulationError('distribution_error', 'Lognormal sigma must be positive');
+    }
+    if (distribution?.type === 'student_t' && !(distribution.df > 2)) {
+      throw new SimulationError('distribution_error', 'Student t degrees of freedom must exceed 2 for a finite variance');
+    }
+    if (distribution?.type !== 'mixture') return;
+
+    const { components } = distribution;
+    if (components.length === 0) {
+      throw new SimulationError('distribution_error', 'Mixture distribution needs at least one component');
+    }
+    if (components.some(c => !(c.weight >= 0))) {
+      throw new SimulationError('distribution_error', 'Mixture weights must be non-negative');
+    }
+    if (components.some(c => !(c.std > 0))) {
+      throw new SimulationError('distribution_error', 'Mixture component standard deviations must be positive');
+    }
+    const total_weight = components.reduce((sum, c) => sum + c.weight, 0);
+    if (Math.abs(total_weight - 1) > 1e-6) {
+      throw new SimulationError('distribution_error', `Mixture weights must sum to 1 (got ${total_weight})`);
+    }
+  }
+
+  // Lower-triangular Cholesky factor L of a symmetric positive-definite matrix (A = L Lᵀ)
+  static choleskyDecomposition(matrix: number[][]): number[][] {
+    const k = matrix.length;
+    const L = Array.from({length: k}, () => new Array(k).fill(0));
+
+    for (let i = 0; i < k; i++) {
+      for (let j = 0; j <= i; j++) {
+        let sum = matrix[i][j];
+        for (let m = 0; m < j; m++) sum -= L[i][m] * L[j][m];
+
+        if (i === j) {
+          if (sum <= 0) {
+            throw new SimulationError('distribution_error', 'Covariance matrix must be positive definite');
+          }
+          L[i][i] = Math.sqrt(sum);
+        } else {
+          L[i][j] = sum / L[j][j];
+        }
+      }
+    }
+
+    return L;
+  }
+
+  // Draw one correlated standard-normal vector z = L e from a Cholesky factor
+  static correlatedNormalVector(cholesky: number[][], random: RandomSource = Math.random): number[] {
+    const e = cholesky.map(() => StatisticalUtils.normalRandom(0, 1, random));
+    return cholesky.map(row => row.reduce((sum, l, m) => sum + l * e[m], 0));
+  }
+
+  // Two-sample t-test using jStat
+  static twoSampleTTest(group1: number[], group2: number[], options: TTestOptions = {}): {
+    t_statistic: number;
+    df: number;
+    p_value: number;
+    effect_size: number;
+    hedges_g: number;
+    confidence_interval: [number, number];
+  } {
+    const n1 = group1.length;
+    const n2 = group2.length;
+    if (n1 < 2 || n2 < 2) {
+      throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+    }
+
+    // Calculate means and variances; the biased 'n' denominator is a teaching option
+    const unbiased = (options.variance_denominator ?? 'n_minus_1') === 'n_minus_1';
+    const mean1 = (jStat as any).mean(group1);
+    const mean2 = (jStat as any).mean(group2);
+    const var1 = (jStat as any).variance(group1, unbiased);
+    const var2 = (jStat as any).variance(group2, unbiased);
+
+    // Pooled standard deviation (equal variances assumed)
+    const pooled_var = unbiased
+      ? ((n1 - 1) * var1 + (n2 - 1) * var2) / (n1 + n2 - 2)
+      : (n1 * var1 + n2 * var2) / (n1 + n2);
+    const pooled_std = Math.sqrt(pooled_var);
+
+    // Standard error and degrees of freedom: pooled assumes equal variances, Welch uses the
+    // unpooled SE with Welch-Satterthwaite df (robust when variances and sizes differ)
+    const welch = options.variance_assumption === 'welch';
+    const se = welch
+      ? Math.sqrt(var1 / n1 + var2 / n2)
+      : pooled_std * Math.sqrt(1/n1 + 1/n2);
+
+    // t-statistic
+    const t_statistic = (mean1 - mean2) / se;
+
+    // Degrees of freedom, optionally floored: very small df give heavy tails and jumpy p-values
+    const raw_df = welch
+      ? StatisticalUtils.welchSatterthwaiteDF(var1, n1, var2, n2)
+      : n1 + n2 - 2;
+    const df = options.min_df === undefined ? raw_df : Math.max(raw_df, options.min_df);
+
+    // p-value using jStat t-distribution (two-tailed unless a direction is given)
+    const p_value = StatisticalUtils.studentTPValue(t_statistic, df, options.alternative);
+
+    // Effect size (Cohen's d) and its small-sample bias-corrected form (Hedges' g)
+    const effect_size = (mean1 - mean2) / pooled_std;
+    const hedges_g = effect_size * StatisticalUtils.hedgesCorrection(n1 + n2 - 2);
+
+    // Confidence interval for effect size using t-distribution
+    const confidence_interval = StatisticalUtils.calculateConfidenceInterval(
+      effect_size, n1, n2, options.confidence_level ?? 0.95);
+
+    return {
+      t_statistic,
+      df,
+      p_value: StatisticalUtils.clampPValue(p_value),
+      effect_size,
+      hedges_g,
+      confidence_interval
+    };
+  }
+
+  // Common language effect size (probability of superiority) on each test's effect scale:
+  // Phi(d / sqrt(2)) for independent-groups d, Phi(d_z) for paired differences and
+  // (r + 1) / 2 for the rank-biserial r. Monotone, so CI endpoints map to CI endpoints
+  static commonLanguageEffectSize(effect_size: number, test_type: TestType = 'pooled'): number {
+    switch (test_type) {
+      case 'mann_whitney':
+        return (effect_size + 1) / 2;
+      case 'paired':
+        return (jStat as any).normal.cdf(effect_size, 0, 1);
+      default:
+        return (jStat as any).normal.cdf(effect_size / Math.SQRT2, 0, 1);
+    }
+  }
+
+  // Hedges' small-sample bias correction factor J for Cohen's d
+  static hedgesCorrection(df: number): number {
+    return 1 - 3 / (4 * df - 1);
+  }
+
+  // Sampling variance of Hedges' g for independent groups from the moments of the noncentral t
+  // (Hedges 1981): J^2 df (1/n1 + 1/n2 + g^2) / (df - 2) - g^2, defined for df > 2
+  static hedgesGVariance(hedges_g: number, n1: number, n2: number): number {
+    const df = n1 + n2 - 2;
+    if (df <= 2) return NaN;
+    const j = StatisticalUtils.hedgesCorrection(df);
+    return j ** 2 * df * (1 / n1 + 1 / n2 + hedges_g ** 2) / (df - 2) - hedges_g ** 2;
+  }
+
+  // (n-1)-weighted pooled standard deviation from summary statistics
+  static pooledStd(sd1: number, n1: number, sd2: number, n2: number): number {
+    if (!(sd1 > 0) || !(sd2 > 0) || !Number.isFinite(sd1) || !Number.isFinite(sd2)) {
+      throw new SimulationError('invalid_std', 'Standard deviations must be positive');
+    }
+    if (!Number.isInteger(n1) || !Number.isInteger(n2) || n1 < 2 || n2 < 2) {
+      throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+    }
+
+    return Math.sqrt(((n1 - 1) * sd1 ** 2 + (n2 - 1) * sd2 ** 2) / (n1 + n2 - 2));
+  }
+
+  // Welch-Satterthwaite degrees of freedom for the unequal-variance t statistic
+  static welchSatterthwaiteDF(var1: number, n1: number, var2: number, n2: number): number {
+    const a = var1 / n1;
+    const b = var2 / n2;
+    return (a + b) ** 2 / (a ** 2 / (n1 - 1) + b ** 2 / (n2 - 1));
+  }
+
+  // Significance levels permutationTest of n1 vs n2 can attain with n_perm permutations. Drawing
+  // n_perm random relabelings gives p = (1 + b) / (n_perm + 1). When n_perm covers all
+  // C = C(n1 + n2, n1) relabelings the test enumerates them instead and p = b / C; a two-sided
+  // test with n1 = n2 counts every relabeling together with its mirror, so p moves in steps of 2 / C
+  static achievableAlphaLevels(
+    n1: number,
+    n2: number,
+    n_perm: number,
+    alternative: Alternative = 'two_sided'
+  ): number[] {
+    if (![n1, n2, n_perm].every(n => Number.isInteger(n) && n >= 1)) {
+      throw new SimulationError('invalid_parameter', 'Group sizes and permutation count must be positive integers');
+    }
+
+    const relabelings = StatisticalUtils.relabelingCount(n1, n2, n_perm);
+    if (relabelings > n_perm) {
+      return Array.from({length: n_perm + 1}, (_, k) => (k + 1) / (n_perm + 1));
+    }
+    const step = alternative === 'two_sided' && n1 === n2 ? 2 : 1;
+    return Array.from({length: Math.floor(relabelings / step)}, (_, k) => (k + 1) * step / relabelings);
+  }
+
+  // C(n1 + n2, n1), the number of distinct relabelings of two groups; Infinity once it passes limit
+  static relabelingCount(n1: number, n2: number, limit: number = Infinity): number {
+    let count = 1;
+    for (let k = 1; k <= Math.min(n1, n2); k++) {
+      count = count * (n1 + n2 - k + 1) / k;
+      if (count > limit) return Infinity;
+    }
+    return Math.round(count);
+  }
+
+  // Two-sided sign test p-values attainable with n non-tied pairs: 2 P(X <= k), X ~ Binomial(n, 1/2)
+  static signTestAlphaLevels(n: number): number[] {
+    if (!Number.isInteger(n) || n < 1) {
+      throw new SimulationError('invalid_parameter', 'Number of pairs must be a positive integer');
+    }
+
+    const levels: number[] = [];
+    let tail = 0;
+    for (let k = 0; k <= Math.floor(n / 2); k++) {
+      tail += Math.exp(StatisticalUtils.logBinomial(n, k) - n * Math.LN2);
+      levels.push(Math.min(1, 2 * tail));
+    }
+    return levels;
+  }
+
+  // Largest attainable level not above the requested alpha (null when none is)
+  static nearestAchievableAlpha(levels: number[], alpha: number): number | null {
+    const attainable = levels.filter(level => level <= alpha);
+    return attainable.length > 0 ? Math.max(...attainable) : null;
+  }
+
+  // log C(n, k) via log-gamma
+  static logBinomial(n: number, k: number): number {
+    return (jStat as any).gammaln(n + 1) - (jStat as any).gammaln(k + 1) - (jStat as any).gammaln(n - k + 1);
+  }
+
+  // t-test p-value for the chosen alternative; 'greater' means group 1's mean is larger
+  static studentTPValue(t_statistic: number, df: number, alternative: Alternative = 'two_sided'): number {
+    switch (alternative) {
+      case 'greater':
+        return 1 - (jStat as any).studentt.cdf(t_statistic, df);
+      case 'less':
+        return (jStat as any).studentt.cdf(t_statistic, df);
+      case 'two_sided':
+        return 2 * (1 - (jStat as any).studentt.cdf(Math.abs(t_statistic), df));
+    }
+  }
+
+  // Paired t-test on within-pair differences on n - 1 df; the effect size is Cohen's
+  // d_z = mean(diff) / sd(diff), with an approximate CI from SE(d_z) = sqrt(1/n + d_z^2 / (2n))
+  static pairedTTest(group1: number[], group2: number[], options: TTestOptions = {}): {
+    t_statistic: number;
+    df: number;
+    p_value: number;
+    effect_size: number;
+    hedges_g: number;
+    confidence_interval: [number, number];
+  } {
+    if (group1.length !== group2.length) {
+      throw new SimulationError('invalid_parameter', 'Paired t-test requires groups of equal length');
+    }
+    const n = group1.length;
+    if (n < 2) {
+      throw new SimulationError('empty_group', 'Paired t-test needs at least 2 pairs');
+    }
+
+    const differences = group1.map((value, i) => value - group2[i]);
+    const mean_diff = (jStat as any).mean(differences);
+    const sd_diff = (jStat as any).stdev(differences, true);
+
+    const t_statistic = mean_diff / (sd_diff / Math.sqrt(n));
+    const df = options.min_df === undefined ? n - 1 : Math.max(n - 1, options.min_df);
+    const p_value = StatisticalUtils.studentTPValue(t_statistic, df, options.alternative);
+
+    const effect_size = mean_diff / sd_diff;
+    const se = Math.sqrt(1 / n + effect_size ** 2 / (2 * n));
+    const t_critical = (jStat as any).studentt.inv(1 - (1 - (options.confidence_level ?? 0.95)) / 2, n - 1);
+
+    return {
+      t_statistic,
+      df,
+      p_value: StatisticalUtils.clampPValue(p_value),
+      effect_size,
+      hedges_g: effect_size * StatisticalUtils.hedgesCorrection(n - 1),
+      confidence_interval: [effect_size - t_critical * se, effect_size + t_critical * se]
+    };
+  }
+
+  // Permutation test on the difference in means: pool both groups, reshuffle the labels
+  // num_permutations times and count relabelings at least as extreme as the observed
+  // difference; p = (1 + b) / (num_permutations + 1), so it is never exactly 0. Small designs
+  // with no more distinct relabelings than num_permutations are enumerated exactly instead,
+  // p = b / C(n1 + n2, n1) with the observed labeling among the b
+  static permutationTest(
+    group1: number[],
+    group2: number[],
+    num_permutations: number,
+    random: RandomSource = Math.random,
+    alternative: Alternative = 'two_sided',
+    deadline: number = Infinity
+  ): { p_value: number; mean_difference: number; permutations: number; exact: boolean } {
+    if (!Number.isInteger(num_permutations) || num_permutations < 1) {
+      throw new SimulationError('invalid_parameter', 'Number of permutations must be a positive integer');
+    }
+
+    const n1 = group1.length;
+    const pooled = [...group1, ...group2];
+    const total = pooled.reduce((sum, value) => sum + value, 0);
+    const differenceFor = (sum1: number) => sum1 / n1 - (total - sum1) / group2.length;
+    const mean_difference = differenceFor(group1.reduce((sum, value) => sum + value, 0));
+
+    // Tolerance keeps relabelings that tie the observed statistic from being lost to rounding
+    const tolerance = 1e-12 * Math.max(1, Math.abs(mean_difference));
+    const isExtreme = (permuted: number) => alternative === 'greater' ? permuted >= mean_difference - tolerance
+      : alternative === 'less' ? permuted <= mean_difference + tolerance
+      : Math.abs(permuted) >= Math.abs(mean_difference) - tolerance;
+
+    // Enumeration is at most num_permutations cheap passes, so the deadline does not apply to it
+    const relabelings = StatisticalUtils.relabelingCount(n1, group2.length, num_permutations);
+    if (relabelings <= num_permutations) {
+      // Visit every n1-subset of the pooled indices in lexicographic order
+      const chosen = Array.from({length: n1}, (_, i) => i);
+      let extreme = 0;
+      for (;;) {
+        const sum1 = chosen.reduce((sum, index) => sum + pooled[index], 0);
+        if (isExtreme(differenceFor(sum1))) extreme++;
+
+        let i = n1 - 1;
+        while (i >= 0 && chosen[i] === pooled.length - n1 + i) i--;
+        if (i < 0) break;
+        chosen[i]++;
+        for (let j = i + 1; j < n1; j++) chosen[j] = chosen[j - 1] + 1;
+      }
+      return { p_value: extreme / relabelings, mean_difference, permutations: relabelings, exact: true };
+    }
+
+    // Past the deadline (a performance.now() timestamp) relabeling stops early, after a floor of
+    // permutations; p then counts only the permutations actually drawn
+    const min_permutations = Math.min(num_permutations, 20);
+    let extreme = 0;
+    let permutations = 0;
+    for (let b = 0; b < num_permutations; b++) {
+      if (b >= min_permutations && b % 16 === 0 && performance.now() > deadline) break;
+      permutations++;
+      // Partial Fisher-Yates: the first n1 slots become the relabeled group 1
+      let sum1 = 0;
+      for (let i = 0; i < n1; i++) {
+        const j = i + Math.floor(random() * (pooled.length - i));
+        [pooled[i], pooled[j]] = [pooled[j], pooled[i]];
+        sum1 += pooled[i];
+      }
+      if (isExtreme(differenceFor(sum1))) extreme++;
+    }
+
+    return {
+      p_value: (1 + extreme) / (permutations + 1),
+      mean_difference,
+      permutations,
+      exact: false
+    };
+  }
+
+  // Mann-Whitney U test: normal approximation with tie and continuity correction.
+  // U is reported for group 1; rank-biserial r = 2U / (n1 n2) - 1 is positive when group 1 ranks higher.
+  static mannWhitneyU(
+    group1: number[],
+    group2: number[],
+    alternative: Alternative = 'two_sided',
+    confidence_level: number = 0.95
+  ): {
+    u_statistic: number;
+    z_statistic: number;
+    p_value: number;
+    rank_biserial: number;
+    rank_biserial_ci: [number, number]; // Wald interval (95% by default) from the variance of U
+  } {
+    const n1 = group1.length;
+    const n2 = group2.length;
+    if (n1 === 0 || n2 === 0) {
+      throw new SimulationError('empty_group', 'Both groups must contain observations');
+    }
+
+    const pooled = [
+      ...group1.map(value => ({ value, group: 1 })),
+      ...group2.map(value => ({ value, group: 2 }))
+    ].sort((a, b) => a.value - b.value);
+    const N = pooled.length;
+
+    // Average ranks across ties, accumulating the tie correction term sum(t^3 - t)
+    let rank_sum1 = 0;
+    let tie_term = 0;
+    for (let i = 0; i < N;) {
+      let j = i;
+      while (j + 1 < N && pooled[j + 1].value === pooled[i].value) j++;
+      const tie_size = j - i + 1;
+      const average_rank = (i + j) / 2 + 1;
+      for (let k = i; k <= j; k++) {
+        if (pooled[k].group === 1) rank_sum1 += average_rank;
+      }
+      tie_term += tie_size ** 3 - tie_size;
+      i = j + 1;
+    }
+
+    const u_statistic = rank_sum1 - n1 * (n1 + 1) / 2;
+    const mean_u = n1 * n2 / 2;
+    const var_u = (n1 * n2 / 12) * ((N + 1) - tie_term / (N * (N - 1)));
+    const deviation = u_statistic - mean_u;
+    const z_statistic = var_u > 0
+      ? (deviation - 0.5 * Math.sign(deviation)) / Math.sqrt(var_u)
+      : 0;
+    // One-sided tails apply the continuity correction toward the null in their own direction
+    let p_value = 2 * (1 - (jStat as any).normal.cdf(Math.abs(z_statistic), 0, 1));
+    if (alternative !== 'two_sided') {
+      const sd_u = Math.sqrt(var_u);
+      p_value = var_u === 0
+        ? 1
+        : alternative === 'greater'
+          ? 1 - (jStat as any).normal.cdf((deviation - 0.5) / sd_u, 0, 1)
+          : (jStat as any).normal.cdf((deviation + 0.5) / sd_u, 0, 1);
+    }
+
+    const rank_biserial = 2 * u_statistic / (n1 * n2) - 1;
+    const z_critical = (jStat as any).normal.inv(1 - (1 - confidence_level) / 2, 0, 1);
+    const rank_biserial_margin = z_critical * 2 * Math.sqrt(var_u) / (n1 * n2);
+
+    return {
+      u_statistic,
+      z_statistic,
+      p_value: StatisticalUtils.clampPValue(p_value),
+      rank_biserial,
+      rank_biserial_ci: [
+        Math.max(-1, rank_biserial - rank_biserial_margin),
+        Math.min(1, rank_biserial + rank_biserial_margin)
+      ]
+    };
+  }
+
+  // Run the t-test and Mann-Whitney test on the same observed data for side-by-side reading
+  static compareTests(group1: number[], group2: number[], alpha: number = 0.05): TestComparison {
+    if (group1.length < 2 || group2.length < 2) {
+      throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+    }
+    if (!(alpha > 0 && alpha < 1)) {
+      throw new SimulationError('invalid_alpha', 'Alpha must be between 0 and 1');
+    }
+
+    const t_test = StatisticalUtils.twoSampleTTest(group1, group2);
+    const mann_whitney = StatisticalUtils.mannWhitneyU(group1, group2);
+    const t_significant = t_test.p_value < alpha;
+    const mw_significant = mann_whitney.p_value < alpha;
+
+    return {
+      t_test: {
+        p_value: t_test.p_value,
+        effect_size: t_test.effect_size, // Cohen's d
+        significant: t_significant
+      },
+      mann_whitney: {
+        p_value: mann_whitney.p_value,
+        effect_size: mann_whitney.rank_biserial,
+        significant: mw_significant
+      },
+      decisions_agree: t_significant === mw_significant
+    };
+  }
+
+  // Analytic confidence interval for Cohen's d using the t critical value
+  static calculateConfidenceInterval(
+    effect_size: number,
+    n1: number,
+    n2: number,
+    confidence_level: number = 0.95
+  ): [number, number] {
+    if (!(confidence_level > 0 && confidence_level < 1)) {
+      throw new SimulationError('invalid_alpha', 'Confidence level must be between 0 and 1');
+    }
+    const margin = StatisticalUtils.effectSizeMarginOfError(n1, n2, confidence_level);
+    return [effect_size - margin, effect_size + margin];
+  }
+
+  // Margin of error of the analytic effect-size CI (independent of d itself)
+  static effectSizeMarginOfError(n1: number, n2: number, confidence_level: number = 0.95): number {
+    const df = n1 + n2 - 2;
+    const t_critical = (jStat as any).studentt.inv(1 - (1 - confidence_level) / 2, df);
+    const se = Math.sqrt(1/n1 + 1/n2);
+    return t_critical * se;
+  }
+
+  // Exact CI for Cohen's d by inverting the noncentral t distribution for the
+  // noncentrality parameter, then rescaling the limits to the d metric
+  static noncentralTCI(
+    effect_size: number,
+    n1: number,
+    n2: number,
+    confidence_level: number = 0.95
+  ): [number, number] {
+    const df = n1 + n2 - 2;
+    const scale = Math.sqrt((n1 * n2) / (n1 + n2));
+    const t_observed = effect_size * scale;
+    const tail = (1 - confidence_level) / 2;
+
+    // P(T <= t_observed | ncp) decreases in ncp; bisect for the ncp giving the target probability
+    const solve = (target: number): number => {
+      const width = 10 + Math.abs(t_observed);
+      let low = t_observed - width;
+      let high = t_observed + width;
+      for (let i = 0; i < 100 && high - low > 1e-10; i++) {
+        const mid = (low + high) / 2;
+        if ((jStat as any).noncentralt.cdf(t_observed, df, mid) > target) {
+          low = mid;
+        } else {
+          high = mid;
+        }
+      }
+      return (low + high) / 2;
+    };
+
+    return [solve(1 - tail) / scale, solve(tail) / scale];
+  }
+
+  // Expected width of the effect-size CI for precision planning without a simulation run
+  static effectSizeCIWidth(
+    effect_size: number,
+    n1: number,
+    n2: number,
+    confidence_level: number = 0.95
+  ): number {
+    if (!Number.isFinite(effect_size)) {
+      throw new SimulationError('invalid_parameter', 'Effect size must be a finite number');
+    }
+    if (n1 < 2 || n2 < 2) {
+      throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+    }
+    if (confidence_level <= 0 || confidence_level >= 1) {
+      throw new SimulationError('invalid_alpha', 'Confidence level must be between 0 and 1');
+    }
+
+    return 2 * StatisticalUtils.effectSizeMarginOfError(n1, n2, confidence_level);
+  }
+
+  // Apply the configured significance rule to a single test outcome
+  static isSignificant(
+    result: { p_value: number; effect_size: number; confidence_interval: [number, number] },
+    alpha: number,
+    rule: SignificanceRule = { type: 'p_value' }
+  ): boolean {
+    const [lower, upper] = result.confidence_interval;
+    const ci_excludes_null = lower > 0 || upper < 0;
+
+    switch (rule.type) {
+      case 'p_value':
+        return result.p_value < alpha;
+      case 'ci_excludes_null':
+        return ci_excludes_null;
+      case 'p_value_and_sesoi':
+        return result.p_value < alpha && Math.abs(result.effect_size) > rule.sesoi;
+      case 'ci_excludes_null_and_sesoi':
+        return ci_excludes_null && Math.abs(result.effect_size) > rule.sesoi;
+    }
+  }
+
+  // Chi-square goodness of fit of a p-value histogram to the uniform distribution. Expected
+  // counts follow each bin's width, so alpha-aligned (unequal) bins are handled too
+  static chiSquareUniformity(histogram: HistogramBin[], total: number): UniformityTest {
+    if (histogram.length < 2) {
+      throw new SimulationError('invalid_parameter', 'Uniformity test needs at least 2 bins');
+    }
+    if (!(total > 0)) {
+      throw new SimulationError('invalid_parameter', 'Uniformity test needs at least one p-value');
+    }
+
+    const statistic = histogram.reduce((sum, bin) => {
+      const expected = total * (bin.bin_end - bin.bin_start);
+      return sum + (bin.count - expected) ** 2 / expected;
+    }, 0);
+    const df = histogram.length - 1;
+
+    return {
+      statistic,
+      df,
+      p_value: 1 - (jStat as any).chisquare.cdf(statistic, df)
+    };
+  }
+
+  // Jarque-Bera normality test from sample skewness and excess kurtosis
+  static jarqueBeraTest(values: number[]): { statistic: number; p_value: number } {
+    const n = values.length;
+    const mean = (jStat as any).mean(values);
+    let m2 = 0, m3 = 0, m4 = 0;
+    for (const v of values) {
+      const d = v - mean;
+      m2 += d ** 2;
+      m3 += d ** 3;
+      m4 += d ** 4;
+    }
+    m2 /= n; m3 /= n; m4 /= n;
+
+    if (m2 === 0) return { statistic: 0, p_value: 1 };
+
+    const skewness = m3 / Math.pow(m2, 1.5);
+    const excess_kurtosis = m4 / (m2 * m2) - 3;
+    const statistic = (n / 6) * (skewness ** 2 + (excess_kurtosis ** 2) / 4);
+    const p_value = StatisticalUtils.clampPValue(1 - (jStat as any).chisquare.cdf(statistic, 2));
+
+    return { statistic, p_value };
+  }
+
+  // Two-sided F test for equality of variances
+  static varianceRatioTest(group1: number[], group2: number[]): { ratio: number; p_value: number } {
+    const ratio = (jStat as any).variance(group1, true) / (jStat as any).variance(group2, true);
+    const cdf = (jStat as any).centralF.cdf(ratio, group1.length - 1, group2.length - 1);
+    const p_value = StatisticalUtils.clampPValue(2 * Math.min(cdf, 1 - cdf));
+
+    return { ratio, p_value };
+  }
+
+  // Suggest pooled t, Welch, or Mann-Whitney from the characteristics of observed data
+  static recommendTest(group1: number[], group2: number[], alpha: number = 0.05): TestRecommendation {
+    if (group1.length < 3 || group2.length < 3) {
+      throw new SimulationError('empty_group', 'Each group needs at least 3 observations to assess normality');
+    }
+
+    const reasons: string[] = [];
+    const normality = [group1, group2].map(g => StatisticalUtils.jarqueBeraTest(g));
+    const non_normal = normality
+      .map((result, i) => ({ group: i + 1, ...result }))
+      .filter(result => result.p_value < alpha);
+
+    if (non_normal.length > 0) {
+      non_normal.forEach(result => {
+        reasons.push(`Group ${result.group} departs from normality (Jarque-Bera p = ${result.p_value.toFixed(4)})`);
+      });
+      reasons.push('A rank-based test does not rely on normally distributed data');
+      return { recommended: 'mann_whitney', reasons };
+    }
+
+    reasons.push('Both groups are consistent with normality');
+
+    const variance_test = StatisticalUtils.varianceRatioTest(group1, group2);
+    if (variance_test.p_value < alpha) {
+      reasons.push(`Group variances differ (variance ratio ${variance_test.ratio.toFixed(3)}, F test p = ${variance_test.p_value.toFixed(4)})`);
+      reasons.push("Welch's t-test does not assume equal variances");
+      return { recommended: 'welch', reasons };
+    }
+
+    reasons.push(`Group variances are compatible (variance ratio ${variance_test.ratio.toFixed(3)})`);
+    return { recommended: 'pooled', reasons };
+  }
+
+  // Cohen's d with pooled standard deviation
+  static cohensD(group1: number[], group2: number[]): number {
+    const n1 = group1.length;
+    const n2 = group2.length;
+    const pooled_var = ((n1 - 1) * (jStat as any).variance(group1, true) +
+      (n2 - 1) * (jStat as any).variance(group2, true)) / (n1 + n2 - 2);
+    return ((jStat as any).mean(group1) - (jStat as any).mean(group2)) / Math.sqrt(pooled_var);
+  }
+
+  // Large-sample standard error of Cohen's d (grows with |d|)
+  static effectSizeSE(effect_size: number, n1: number, n2: number): number {
+    return Math.sqrt((n1 + n2) / (n1 * n2) + effect_size ** 2 / (2 * (n1 + n2)));
+  }
+
+  // Draw a same-size sample with replacement
+  static resample(values: number[], random: RandomSource = Math.random): number[] {
+    const n = values.length;
+    return Array.from({length: n}, () => values[Math.floor(random() * n)]);
+  }
+
+  // Linearly interpolated percentile (q in [0, 1]) of an ascending-sorted array
+  static percentile(sorted_values: number[], q: number): number {
+    if (sorted_values.length === 0) return NaN;
+    const position = q * (sorted_values.length - 1);
+    const lower = Math.floor(position);
+    const upper = Math.ceil(position);
+    return sorted_values[lower] + (position - lower) * (sorted_values[upper] - sorted_values[lower]);
+  }
+
+  // Monte Carlo standard error of a bootstrap percentile via a delete-a-group jackknife over
+  // the replicates themselves: drop each of num_groups interleaved groups, recompute the
+  // percentile, and scale the spread. Shrinks roughly as 1 / sqrt(replicates)
+  static jackknifeQuantileSE(replicates: number[], q: number, num_groups: number = 20): number {
+    const groups = Math.min(num_groups, replicates.length);
+    if (groups < 2) return NaN;
+
+    const estimates: number[] = [];
+    for (let g = 0; g < groups; g++) {
+      const kept = replicates.filter((_, k) => k % groups !== g).sort((a, b) => a - b);
+      estimates.push(StatisticalUtils.percentile(kept, q));
+    }
+    const mean = estimates.reduce((sum, value) => sum + value, 0) / groups;
+    const spread = estimates.reduce((sum, value) => sum + (value - mean) ** 2, 0);
+    return Math.sqrt((groups - 1) / groups * spread);
+  }
+
+  // Studentized (percentile-t) bootstrap CI for Cohen's d: bootstraps (d* - d) / se*
+  // and inverts its quantiles around the observed d. estimate_mc_error adds the jackknife
+  // Monte Carlo error of each endpoint
+  static bootstrapTCI(
+    group1: number[],
+    group2: number[],
+    n_boot: number,
+    confidence_level: number = 0.95,
+    random: RandomSource = Math.random,
+    deadline: number = Infinity,
+    estimate_mc_error: boolean = false
+  ): { confidence_interval: [number, number]; resamples: number; endpoint_mc_error?: [number, number] } {
+    const n1 = group1.length;
+    const n2 = group2.length;
+    const d = StatisticalUtils.cohensD(group1, group2);
+    const se = StatisticalUtils.effectSizeSE(d, n1, n2);
+
+    // Past the deadline (a performance.now() timestamp) resampling stops early, after a floor
+    // of resamples; fewer resamples coarsen the tail percentiles the interval is built from
+    const min_resamples = Math.min(n_boot, 20);
+    const t_stars: number[] = [];
+    let resamples = 0;
+    for (let b = 0; b < n_boot; b++) {
+      if (b >= min_resamples && b % 16 === 0 && performance.now() > deadline) break;
+      resamples++;
+      const d_star = StatisticalUtils.cohensD(
+        StatisticalUtils.resample(group1, random),
+        StatisticalUtils.resample(group2, random)
+      );
+      const t_star = (d_star - d) / StatisticalUtils.effectSizeSE(d_star, n1, n2);
+      // Degenerate resamples (zero variance) carry no information
+      if (Number.isFinite(t_star)) t_stars.push(t_star);
+    }
+
+    const tail = (1 - confidence_level) / 2;
+    // The jackknife groups need the replicates in draw order, so this precedes the sort
+    const endpoint_mc_error: [number, number] | undefined = estimate_mc_error
+      ? [
+        StatisticalUtils.jackknifeQuantileSE(t_stars, 1 - tail) * se,
+        StatisticalUtils.jackknifeQuantileSE(t_stars, tail) * se
+      ]
+      : undefined;
+    t_stars.sort((a, b) => a - b);
+
+    return {
+      confidence_interval: [
+        d - StatisticalUtils.percentile(t_stars, 1 - tail) * se,
+        d - StatisticalUtils.percentile(t_stars, tail) * se
+      ],
+      resamples,
+      endpoint_mc_error
+    };
+  }
+
+  // Percentile bootstrap CI for Cohen's d: resample each group with replacement, recompute d,
+  // and take the tail percentiles of the resampled d values. Stops early past the deadline
+  // (a performance.now() timestamp) and estimates endpoint MC error the same way as bootstrapTCI
+  static bootstrapPercentileCI(
+    group1: number[],
+    group2: number[],
+    n_boot: number = DEFAULT_BOOTSTRAP_RESAMPLES,
+    confidence_level: number = 0.95,
+    random: RandomSource = Math.random,
+    deadline: number = Infinity,
+    estimate_mc_error: boolean = false
+  ): { confidence_interval: [number, number]; resamples: number; endpoint_mc_error?: [number, number] } {
+    const min_resamples = Math.min(n_boot, 20);
+    const d_stars: number[] = [];
+    let resamples = 0;
+    for (let b = 0; b < n_boot; b++) {
+      if (b >= min_resamples && b % 16 === 0 && performance.now() > deadline) break;
+      resamples++;
+      const d_star = StatisticalUtils.cohensD(
+        StatisticalUtils.resample(group1, random),
+        StatisticalUtils.resample(group2, random)
+      );
+      if (Number.isFinite(d_star)) d_stars.push(d_star);
+    }
+
+    const tail = (1 - confidence_level) / 2;
+    const endpoint_mc_error: [number, number] | undefined = estimate_mc_error
+      ? [StatisticalUtils.jackknifeQuantileSE(d_stars, tail), StatisticalUtils.jackknifeQuantileSE(d_stars, 1 - tail)]
+      : undefined;
+    d_stars.sort((a, b) => a - b);
+
+    return {
+      confidence_interval: [
+        StatisticalUtils.percentile(d_stars, tail),
+        StatisticalUtils.percentile(d_stars, 1 - tail)
+      ],
+      resamples,
+      endpoint_mc_error
+    };
+  }
+
+  // Wilson score interval for a binomial proportion such as power; narrower than
+  // Clopper-Pearson and well behaved near 0 and 1
+  static powerWilsonCI(
+    significant_count: number,
+    total_count: number,
+    confidence_level: number = 0.95
+  ): [number, number] {
+    if (!Number.isInteger(total_count) || total_count < 1) {
+      throw new SimulationError('invalid_parameter', 'Total count must be a positive integer');
+    }
+    if (!Number.isInteger(significant_count) || significant_count < 0 || significant_count > total_count) {
+      throw new SimulationError('invalid_parameter', 'Significant count must be an integer between 0 and the total count');
+    }
+    if (confidence_level <= 0 || confidence_level >= 1) {
+      throw new SimulationError('invalid_alpha', 'Confidence level must be between 0 and 1');
+    }
+
+    const z = (jStat as any).normal.inv(1 - (1 - confidence_level) / 2, 0, 1);
+    const n = total_count;
+    const p = significant_count / n;
+    const denominator = 1 + z ** 2 / n;
+    const centre = (p + z ** 2 / (2 * n)) / denominator;
+    const half_width = z * Math.sqrt(p * (1 - p) / n + z ** 2 / (4 * n ** 2)) / denominator;
+
+    return [Math.max(0, centre - half_width), Math.min(1, centre + half_width)];
+  }
+
+  // Exact (Clopper-Pearson) binomial CI for power estimated as significant_count / total_count
+  static powerExactCI(
+    significant_count: number,
+    total_count: number,
+    confidence_level: number = 0.95
+  ): [number, number] {
+    if (!Number.isInteger(total_count) || total_count < 1) {
+      throw new SimulationError('invalid_parameter', 'Total count must be a positive integer');
+    }
+    if (!Number.isInteger(significant_count) || significant_count < 0 || significant_count > total_count) {
+      throw new SimulationError('invalid_parameter', 'Significant count must be an integer between 0 and the total count');
+    }
+    if (confidence_level <= 0 || confidence_level >= 1) {
+      throw new SimulationError('invalid_alpha', 'Confidence level must be between 0 and 1');
+    }
+
+    const tail = (1 - confidence_level) / 2;
+    const k = significant_count;
+    const n = total_count;
+    const lower = k === 0 ? 0 : (jStat as any).beta.inv(tail, k, n - k + 1);
+    const upper = k === n ? 1 : (jStat as any).beta.inv(1 - tail, k + 1, n - k);
+
+    return [lower, upper];
+  }
+
+  // Conjugate normal-normal update for the standardized effect: normal prior on d,
+  // normal likelihood centred on the observed d with its large-sample SE
+  static posteriorEffectSize(
+    group1: number[],
+    group2: number[],
+    prior_mean: number,
+    prior_sd: number
+  ): PosteriorEffectSize {
+    if (group1.length < 2 || group2.length < 2) {
+      throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+    }
+    if (!Number.isFinite(prior_mean)) {
+      throw new SimulationError('invalid_parameter', 'Prior mean must be a finite number');
+    }
+    if (!(prior_sd > 0)) {
+      throw new SimulationError('invalid_parameter', 'Prior standard deviation must be positive');
+    }
+
+    const observed_effect_size = StatisticalUtils.cohensD(group1, group2);
+    const observed_se = StatisticalUtils.effectSizeSE(observed_effect_size, group1.length, group2.length);
+
+    const prior_precision = 1 / prior_sd ** 2;
+    const data_precision = 1 / observed_se ** 2;
+    const posterior_precision = prior_precision + data_precision;
+    const posterior_mean = (prior_mean * prior_precision + observed_effect_size * data_precision) / posterior_precision;
+    const posterior_sd = Math.sqrt(1 / posterior_precision);
+    const z = (jStat as any).normal.inv(0.975, 0, 1);
+
+    return {
+      observed_effect_size,
+      observed_se,
+      posterior_mean,
+      posterior_sd,
+      credible_interval: [posterior_mean - z * posterior_sd, posterior_mean + z * posterior_sd]
+    };
+  }
+
+  // Monte Carlo iterations needed so the normal-approximation CI for an estimated power
+  // has half-width at most target_half_width: n = z^2 p (1 - p) / h^2
+  static simulationsForPowerPrecision(
+    expected_power: number,
+    target_half_width: number,
+    confidence_level: number = 0.95
+  ): number {
+    if (!(expected_power >= 0 && expected_power <= 1)) {
+      throw new SimulationError('invalid_parameter', 'Expected power must be between 0 and 1');
+    }
+    if (!(target_half_width > 0 && target_half_width < 1)) {
+      throw new SimulationError('invalid_parameter', 'Target half-width must be between 0 and 1');
+    }
+    if (!(confidence_level > 0 && confidence_level < 1)) {
+      throw new SimulationError('invalid_alpha', 'Confidence level must be between 0 and 1');
+    }
+
+    const z = (jStat as any).normal.inv(1 - (1 - confidence_level) / 2, 0, 1);
+    const variance = expected_power * (1 - expected_power);
+    return Math.max(1, Math.ceil(z * z * variance / (target_half_width * target_half_width)));
+  }
+
+  // Overlap between two intervals ("do the error bars overlap?"). Caveat: non-overlapping
+  // 95% CIs imply a significant difference, but overlapping CIs do NOT imply non-significance.
+  static ciOverlap(ci_a: [number, number], ci_b: [number, number]): { overlap: boolean; overlap_amount: number } {
+    for (const [lower, upper] of [ci_a, ci_b]) {
+      if (!Number.isFinite(lower) || !Number.isFinite(upper) || lower > upper) {
+        throw new SimulationError('invalid_parameter', 'Intervals must be finite with lower <= upper');
+      }
+    }
+
+    const overlap_amount = Math.min(ci_a[1], ci_b[1]) - Math.max(ci_a[0], ci_b[0]);
+    return {
+      overlap: overlap_amount >= 0,
+      overlap_amount: Math.max(0, overlap_amount)
+    };
+  }
+
+  // Number of p-values significant at alpha after treating them as one family: Bonferroni tests
+  // each at alpha / m; Benjamini-Hochberg rejects the k smallest for the largest k with
+  // p_(k) < k alpha / m. 'none' is the plain p < alpha count
+  static adjustedSignificantCount(p_values: number[], alpha: number, method: MultipleComparison): number {
+    const m = p_values.length;
+    switch (method) {
+      case 'none':
+        return p_values.filter(p => p < alpha).length;
+      case 'bonferroni':
+        return p_values.filter(p => p < alpha / m).length;
+      case 'benjamini_hochberg': {
+        const sorted = [...p_values].sort((a, b) => a - b);
+        for (let k = m; k >= 1; k--) {
+          if (sorted[k - 1] < k * alpha / m) return k;
+        }
+        return 0;
+      }
+    }
+  }
+
+  // Storey's null proportion from the count of p-values above lambda among m, capped at 1
+  static storeyPi0(count_above_lambda: number, m: number, lambda: number): number {
+    return m === 0 ? 1 : Math.min(1, count_above_lambda / (m * (1 - lambda)));
+  }
+
+  // Storey's q-values: BH step-up scaled by the estimated null proportion
+  // pi0 = #{p > lambda} / (m (1 - lambda)), capped at 1; q-values keep the input order
+  static storeyQValues(p_values: number[], lambda: number = 0.5): { q_values: number[]; pi0: number } {
+    if (!(lambda >= 0 && lambda < 1)) {
+      throw new SimulationError('invalid_parameter', 'Storey lambda must be in [0, 1)');
+    }
+    const m = p_values.length;
+    if (m === 0) return { q_values: [], pi0: 1 };
+
+    const pi0 = StatisticalUtils.storeyPi0(p_values.filter(p => p > lambda).length, m, lambda);
+    const order = p_values.map((_, i) => i).sort((a, b) => p_values[a] - p_values[b]);
+    const q_values = new Array<number>(m);
+    let running_min = 1;
+    for (let rank = m; rank >= 1; rank--) {
+      const index = order[rank - 1];
+      running_min = Math.min(running_min, pi0 * m * p_values[index] / rank);
+      q_values[index] = running_min;
+    }
+
+    return { q_values, pi0 };
+  }
+
+  // Verbal label for |d| using ascending small/medium/large cut-offs (Cohen's by default)
+  static classifyEffectSize(d: number, thresholds: [number, number, number] = [0.2, 0.5, 0.8]): EffectSizeClass {
+    const magnitude = Math.abs(d);
+    if (magnitude < thresholds[0]) return 'negligible';
+    if (magnitude < thresholds[1]) return 'small';
+    if (magnitude < thresholds[2]) return 'medium';
+    return 'large';
+  }
+
+  // Binomial variance p(1 - p) over the per-simulation variance implied by sub-batch power
+  // estimates; batches hold whole antithetic pairs. Null when the ratio is undefined
+  static varianceReductionFactor(outcomes: boolean[], num_batches: number = 20): number | null {
+    const batch_size = StatisticalUtils.varianceReductionBatchSize(outcomes.length, num_batches);
+    const batch_counts = Array.from({length: num_batches}, (_, b) =>
+      outcomes.slice(b * batch_size, (b + 1) * batch_size).filter(Boolean).length);
+    return StatisticalUtils.varianceReductionFromBatches(batch_counts, batch_size);
+  }
+
+  // Even sub-batch size, so batches hold whole antithetic pairs; 0 when there are too few outcomes
+  static varianceReductionBatchSize(num_outcomes: number, num_batches: number = 20): number {
+    return 2 * Math.floor(num_outcomes / (2 * num_batches));
+  }
+
+  // varianceReductionFactor from the significant count of each sub-batch
+  static varianceReductionFromBatches(batch_counts: number[], batch_size: number): number | null {
+    if (batch_size === 0) return null;
+
+    const batch_rates = batch_counts.map(count => count / batch_size);
+    const observed_variance = batch_size * (jStat as any).variance(batch_rates, true);
+    const rate = (jStat as any).mean(batch_rates);
+    const binomial_variance = rate * (1 - rate);
+
+    return observed_variance > 0 ? binomial_variance / observed_variance : null;
+  }
+
+  // Two-tailed formulas like 2 * (1 - cdf) can round slightly outside [0, 1]; NaN passes through
+  static clampPValue(p_value: number): number {
+    return Math.max(0, Math.min(1, p_value));
+  }
+
+  // Calculate S-value (Shannon information); clamping first keeps it non-negative at p >= 1
+  static calculateSValue(p_value: number): number {
+    const p = StatisticalUtils.clampPValue(p_value);
+    if (p === 0) return Infinity;
+    if (p === 1) return 0;
+    return -Math.log2(p);
+  }
+
+  // z-score whose (upper-tail or two-sided) normal p-value equals p
+  static pValueToZ(p_value: number, two_sided: boolean = true): number {
+    if (!(p_value > 0 && p_value <= 1)) {
+      throw new SimulationError('invalid_parameter', 'p-value must be in (0, 1]');
+    }
+    return (jStat as any).normal.inv(1 - (two_sided ? p_value / 2 : p_value), 0, 1);
+  }
+
+  // Combine independent p-values: Fisher's -2 sum(ln p) ~ chi-square(2m), Stouffer's
+  // sum(z) / sqrt(m) with one-sided z-scores, or the harmonic mean p-value m / sum(1 / p)
+  // (the plain HMP, which is approximately valid for small combined values)
+  static combinePValues(p_values: number[], method: CombineMethod): number {
+    if (p_values.length === 0) {
+      throw new SimulationError('invalid_parameter', 'At least one p-value is required');
+    }
+    if (p_values.some(p => !(p > 0 && p <= 1))) {
+      throw new SimulationError('invalid_parameter', 'p-values must be in (0, 1]');
+    }
+    const m = p_values.length;
+
+    switch (method) {
+      case 'fisher': {
+        const statistic = -2 * p_values.reduce((sum, p) => sum + Math.log(p), 0);
+        return StatisticalUtils.clampPValue(1 - (jStat as any).chisquare.cdf(statistic, 2 * m));
+      }
+      case 'stouffer': {
+        const z = p_values.reduce((sum, p) => sum + StatisticalUtils.pValueToZ(p, false), 0) / Math.sqrt(m);
+        return StatisticalUtils.zToPValue(z, false);
+      }
+      case 'harmonic_mean':
+        return Math.min(1, m / p_values.reduce((sum, p) => sum + 1 / p, 0));
+    }
+  }
+
+  // Normal p-value for a z-score (two-sided uses |z|, one-sided is the upper tail)
+  static zToPValue(z: number, two_sided: boolean = true): number {
+    if (Number.isNaN(z)) {
+      throw new SimulationError('invalid_parameter', 'z-score must be a number');
+    }
+    const p_value = two_sided
+      ? 2 * (1 - (jStat as any).normal.cdf(Math.abs(z), 0, 1))
+      : 1 - (jStat as any).normal.cdf(z, 0, 1);
+    return StatisticalUtils.clampPValue(p_value);
+  }
+
+  // Re-express the same evidence as a p-value, z-score, or S-value (bits)
+  static convertStatistic(
+    value: number,
+    from: StatisticKind,
+    to: StatisticKind,
+    two_sided: boolean = true
+  ): number {
+    let p_value: number;
+    switch (from) {
+      case 'p_value':
+        if (!(value >= 0 && value <= 1)) throw new SimulationError('invalid_parameter', 'p-value must be in [0, 1]');
+        p_value = value;
+        break;
+      case 'z_score':
+        p_value = StatisticalUtils.zToPValue(value, two_sided);
+        break;
+      case 's_value':
+        if (!(value >= 0)) throw new SimulationError('invalid_parameter', 'S-value must be non-negative');
+        p_value = Math.pow(2, -value);
+        break;
+    }
+
+    switch (to) {
+      case 'p_value':
+        return p_value;
+      case 'z_score':
+        return p_value === 0 ? Infinity : StatisticalUtils.pValueToZ(p_value, two_sided);
+      case 's_value':
+        return StatisticalUtils.calculateSValue(p_value);
+    }
+  }
+
+  // Analytic power of the pooled t-test with n per group from the noncentral t distribution.
+  // For one-sided alternatives a positive d means group 1's mean is larger
+  static analyticPower(
+    effect_size: number,
+    n: number,
+    alpha: number,
+    alternative: Alternative = 'two_sided'
+  ): number {
+    const df = 2 * n - 2;
+    const ncp = effect_size * Math.sqrt(n / 2);
+    const cdf = (t: number) => (jStat as any).noncentralt.cdf(t, df, ncp);
+
+    switch (alternative) {
+      case 'greater':
+        return 1 - cdf((jStat as any).studentt.inv(1 - alpha, df));
+      case 'less':
+        return cdf(-(jStat as any).studentt.inv(1 - alpha, df));
+      case 'two_sided': {
+        const t_critical = (jStat as any).studentt.inv(1 - alpha / 2, df);
+        return 1 - cdf(t_critical) + cdf(-t_critical);
+      }
+    }
+  }
+
+  // Expected p-value of the pooled t-test when the true standardized effect is d:
+  // E[p] = integral of p(t) over the noncentral t density (Simpson's rule)
+  static expectedPValue(effect_size: number, n1: number, n2: number, two_sided: boolean = true): number {
+    const df = n1 + n2 - 2;
+    const ncp = effect_size * Math.sqrt((n1 * n2) / (n1 + n2));
+    const p_of = (t: number) => two_sided
+      ? 2 * (1 - (jStat as any).studentt.cdf(Math.abs(t), df))
+      : 1 - (jStat as any).studentt.cdf(t, df);
+
+    const half_range = 12 * Math.sqrt(df / Math.max(df - 2, 1)) + Math.abs(ncp) * 0.5;
+    const lower = ncp - half_range;
+    const upper = ncp + half_range;
+    const steps = 2000;
+    const h = (upper - lower) / steps;
+
+    let sum = 0;
+    for (let i = 0; i <= steps; i++) {
+      const t = lower + i * h;
+      const weight = i === 0 || i === steps ? 1 : (i % 2 === 0 ? 2 : 4);
+      sum += weight * p_of(t) * (jStat as any).noncentralt.pdf(t, df, ncp);
+    }
+    return StatisticalUtils.clampPValue(sum * h / 3);
+  }
+
+  // JZS Bayes factor BF10 for the two-sample t-test (Rouder et al. 2009): Cauchy(0, r_scale)
+  // prior on delta, written as a normal mixed over g ~ inverse-gamma(1/2, 1/2). The integral over
+  // g is done in log space on u = log g (trapezoid), so extreme t values cannot overflow
+  // before the final exponent; BF10 itself is Infinity only past ~1e308
+  static jzsBayesFactor(t_statistic: number, n1: number, n2: number, r_scale: number = Math.SQRT1_2): number {
+    if (!Number.isFinite(t_statistic)) {
+      throw new SimulationError('invalid_parameter', 't statistic must be a finite number');
+    }
+    if (!Number.isInteger(n1) || !Number.isInteger(n2) || n1 < 2 || n2 < 2) {
+      throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+    }
+
+    const n_eff = n1 * n2 / (n1 + n2);
+    const nu = n1 + n2 - 2;
+    const t2 = t_statistic ** 2;
+    const log_null = -(nu + 1) / 2 * Math.log1p(t2 / nu);
+    const logIntegrand = (u: number) => {
+      const g = Math.exp(u);
+      const spread = 1 + n_eff * g * r_scale ** 2;
+      // The extra u is the Jacobian dg = g du
+      return -0.5 * Math.log(spread) - (nu + 1) / 2 * Math.log1p(t2 / (spread * nu)) -
+        0.5 * Math.log(2 * Math.PI) - 1.5 * u - 1 / (2 * g) + u;
+    };
+
+    // The integrand peaks near g ~ t^2 / (n_eff r^2), so the upper limit grows with |t|
+    const lower = -12;
+    const upper = Math.max(15, Math.log1p(t2) + 10);
+    const steps = 600;
+    const h = (upper - lower) / steps;
+    const log_values = Array.from({length: steps + 1}, (_, i) => logIntegrand(lower + i * h));
+    const peak = Math.max(...log_values);
+    let sum = 0;
+    log_values.forEach((value, i) => {
+      sum += (i === 0 || i === steps ? 0.5 : 1) * Math.exp(value - peak);
+    });
+
+    return Math.exp(peak + Math.log(sum * h) - log_null);
+  }
+
+  // Glass's delta: mean difference over the control group's SD alone (group 2 by default),
+  // with the large-sample variance (n1 + n2) / (n1 n2) + delta^2 / (2 (n_control - 1))
+  static glassDelta(
+    group1: number[],
+    group2: number[],
+    control_group: 1 | 2 = 2,
+    confidence_level: number = 0.95
+  ): { effect_size: number; confidence_interval: [number, number] } {
+    const n1 = group1.length;
+    const n2 = group2.length;
+    const control = control_group === 1 ? group1 : group2;
+    const effect_size = ((jStat as any).mean(group1) - (jStat as any).mean(group2)) /
+      (jStat as any).stdev(control, true);
+
+    const se = Math.sqrt((n1 + n2) / (n1 * n2) + effect_size ** 2 / (2 * (control.length - 1)));
+    const z_critical = (jStat as any).normal.inv(1 - (1 - confidence_level) / 2, 0, 1);
+    return {
+      effect_size,
+      confidence_interval: [effect_size - z_critical * se, effect_size + z_critical * se]
+    };
+  }
+
+  // Critical effect size: the smallest observed |d| the pooled t-test declares significant at
+  // alpha, t_crit(df) * sqrt(1/n1 + 1/n2). A reference line for effect-size plots
+  static criticalEffectSize(n1: number, n2: number, alpha: number, two_sided: boolean = true): number {
+    if (!Number.isInteger(n1) || !Number.isInteger(n2) || n1 < 2 || n2 < 2) {
+      throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+    }
+    if (!(alpha > 0 && alpha < 1)) {
+      throw new SimulationError('invalid_alpha', 'Alpha must be between 0 and 1');
+    }
+
+    const t_critical = (jStat as any).studentt.inv(1 - (two_sided ? alpha / 2 : alpha), n1 + n2 - 2);
+    return t_critical * Math.sqrt(1 / n1 + 1 / n2);
+  }
+
+  // Smallest effect size whose expected p-value carries target_s bits of evidence
+  // (S = -log2 E[p]). Under no effect E[p] = 0.5, so targets of 1 bit or less need d = 0.
+  static effectSizeForSValue(target_s: number, n1: number, n2: number, two_sided: boolean = true): number {
+    if (!(target_s > 0) || !Number.isFinite(target_s)) {
+      throw new SimulationError('invalid_parameter', 'Target S-value must be a positive number');
+    }
+    if (!Number.isInteger(n1) || !Number.isInteger(n2) || n1 < 2 || n2 < 2) {
+      throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+    }
+
+    const target_p = Math.pow(2, -target_s);
+    if (target_p >= StatisticalUtils.expectedPValue(0, n1, n2, two_sided)) return 0;
+
+    // E[p] decreases in d, so bisect
+    let low = 0;
+    let high = 1;
+    while (StatisticalUtils.expectedPValue(high, n1, n2, two_sided) > target_p) {
+      high *= 2;
+      if (high > 100) {
+        throw new SimulationError('invalid_parameter', 'Target S-value is unreachable for this design');
+      }
+    }
+    for (let i = 0; i < 50 && high - low > 1e-6; i++) {
+      const mid = (low + high) / 2;
+      if (StatisticalUtils.expectedPValue(mid, n1, n2, two_sided) > target_p) {
+        low = mid;
+      } else {
+        high = mid;
+      }
+    }
+    return (low + high) / 2;
+  }
+
+  // Bin edges of the p-value histogram. With align_to_alpha an extra edge is inserted at alpha
+  // (unless one already falls there), splitting the straddling bin into two narrower bins
+  // so the significance shading changes exactly at alpha
+  static pValueHistogramEdges(alpha: number = 0.05, num_bins: number = 20, align_to_alpha: boolean = false): number[] {
+    const edges = Array.from({length: num_bins + 1}, (_, i) => i / num_bins);
+    if (align_to_alpha && alpha > 0 && alpha < 1 && !edges.some(edge => Math.abs(edge - alpha) < 1e-12)) {
+      edges.splice(edges.findIndex(edge => edge > alpha), 0, alpha);
+    }
+    return edges;
+  }
+
+  // Bin of p among those edges: [start, end), with the last bin closed so 1.0 is counted.
+  // -1 for values outside [0, 1] (or NaN)
+  static pValueBinIndex(edges: number[], p: number): number {
+    const bin_count = edges.length - 1;
+    if (!(p >= edges[0] && p <= edges[bin_count])) return -1;
+    for (let i = 0; i < bin_count - 1; i++) {
+      if (p < edges[i + 1]) return i;
+    }
+    return bin_count - 1;
+  }
+
+  // p-value histogram from per-bin counts over pValueHistogramEdges
+  static pValueHistogramFromCounts(edges: number[], counts: number[], alpha: number): Array<{
+    bin_start: number;
+    bin_end: number;
+    count: number;
+    significant: boolean;
+  }> {
+    return counts.map((count, i) => ({
+      bin_start: edges[i],
+      bin_end: edges[i + 1],
+      count,
+      significant: edges[i + 1] <= alpha
+    }));
+  }
+
+  static createPValueHistogram(
+    p_values: number[],
+    alpha: number = 0.05,
+    num_bins: number = 20,
+    align_to_alpha: boolean = false
+  ): Array<{
+    bin_start: number;
+    bin_end: number;
+    count: number;
+    significant: boolean;
+  }> {
+    const edges = StatisticalUtils.pValueHistogramEdges(alpha, num_bins, align_to_alpha);
+    const counts = new Array<number>(edges.length - 1).fill(0);
+    for (const p of p_values) {
+      const bin = StatisticalUtils.pValueBinIndex(edges, p);
+      if (bin >= 0) counts[bin]++;
+    }
+    return StatisticalUtils.pValueHistogramFromCounts(edges, counts, alpha);
+  }
+
+  // Bin arbitrary values into equal-width bins over their observed range
+  static createHistogram(values: number[], num_bins: number = 20): ValueHistogramBin[] {
+    if (values.length === 0) return [];
+
+    const min = values.reduce((a, b) => Math.min(a, b), Infinity);
+    const max = values.reduce((a, b) => Math.max(a, b), -Infinity);
+
+    if (max === min) {
+      return [{ bin_start: min, bin_end: max, count: values.length }];
+    }
+
+    const width = (max - min) / num_bins;
+    const counts = new Array(num_bins).fill(0);
+    for (const v of values) {
+      // Maximum value falls into the final bin
+      counts[Math.min(num_bins - 1, Math.floor((v - min) / width))]++;
+    }
+
+    return counts.map((count, i) => ({
+      bin_start: min + i * width,
+      bin_end: i === num_bins - 1 ? max : min + (i + 1) * width,
+      count
+    }));
+  }
+
+  // Approximate createHistogram for streamed values: the same equal-width bins over the exact
+  // range, with counts read off the digest's CDF (cumulative rounding keeps the total exact)
+  static createHistogramFromDigest(digest: TDigest, num_bins: number = 20): ValueHistogramBin[] {
+    const total = digest.count;
+    if (total === 0) return [];
+
+    const min = digest.quantile(0);
+    const max = digest.quantile(1);
+    if (max === min) {
+      return [{ bin_start: min, bin_end: max, count: total }];
+    }
+
+    const width = (max - min) / num_bins;
+    const cumulative = (i: number) => i === num_bins ? total : Math.round(total * digest.cdf(min + i * width));
+    return Array.from({length: num_bins}, (_, i) => ({
+      bin_start: min + i * width,
+      bin_end: i === num_bins - 1 ? max : min + (i + 1) * width,
+      count: cumulative(i + 1) - (i === 0 ? 0 : cumulative(i))
+    }));
+  }
+
+  // Fixed percentile set (min, 5, 25, 50, 75, 95, max) from any quantile function
+  static summarizeQuantiles(quantile: (q: number) => number): QuantileSummary {
+    return {
+      min: quantile(0),
+      p5: quantile(0.05),
+      p25: quantile(0.25),
+      p50: quantile(0.5),
+      p75: quantile(0.75),
+      p95: quantile(0.95),
+      max: quantile(1)
+    };
+  }
+
+  // Quantile summary of an unsorted array
+  static summarizeValues(values: number[]): QuantileSummary {
+    const sorted = [...values].sort((a, b) => a - b);
+    return StatisticalUtils.summarizeQuantiles(q => StatisticalUtils.percentile(sorted, q));
+  }
+
+  // Calculate confidence interval coverage
+  static calculateCICoverage(
+    true_value: number,
+    confidence_intervals: Array<[number, number]>
+  ): number {
+    let coverage_count = 0;
+    for (const [lower, upper] of confidence_intervals) {
+      if (true_value >= lower && true_value <= upper) {
+        coverage_count++;
+      }
+    }
+    return coverage_count / confidence_intervals.length;
+  }
+
+  // Calculate mean confidence interval width
+  static calculateMeanCIWidth(confidence_intervals: Array<[number, number]>): number {
+    const widths = confidence_intervals.map(([lower, upper]) => upper - lower);
+    return jStat.mean(widths);
+  }
+}
+
+// Production-ready simulation function using jStat
+export async function runStatisticalSimulation(
+  params: SimulationParams,
+  hooks: SimulationHooks = {}
+): Promise<AggregatedResults> {
+  if (params.verify_reproducibility && params.seed !== undefined) {
+    // Two independent runs from the same seed must agree exactly; only the first is observed
+    const first = await executeSimulation(params, hooks);
+    const second = await executeSimulation(params);
+
+    if (!resultsAreIdentical(first, second)) {
+      throw new SimulationError('reproducibility_failure',
+        `Reproducibility check failed: two runs with seed ${params.seed} produced different results`);
+    }
+
+    return first;
+  }
+
+  return executeSimulation(params, hooks);
+}
+
+// Run several parameter sets in order. How their seeds relate decides what a comparison means:
+// 'shared' reuses one base seed so sampling noise is common and differences isolate the
+// parameters; 'per_index' uses base_seed + i; 'independent' leaves every set unseeded.
+// Without a base seed, 'shared' and 'per_index' draw one at random for the whole batch
+export async function runSimulationBatch(
+  param_sets: SimulationParams[],
+  seeding: BatchSeeding = 'shared',
+  base_seed?: number
+): Promise<AggregatedResults[]> {
+  if (param_sets.length === 0) {
+    throw new SimulationError('invalid_parameter', 'At least one parameter set is required');
+  }
+
+  const seed = base_seed ?? Math.floor(Math.random() * 4294967296);
+  const results: AggregatedResults[] = [];
+  for (let i = 0; i < param_sets.length; i++) {
+    const set_seed = seeding === 'shared' ? seed
+      : seeding === 'per_index' ? seed + i
+      : undefined;
+    results.push(await runStatisticalSimulation({ ...param_sets[i], seed: set_seed }));
+  }
+  return results;
+}
+
+// Live feed of a run: per-simulation results in batches interleaved with progress updates,
+// then the aggregates
+export async function runStatisticalSimulationStreamed(
+  params: SimulationParams,
+  onEvent: (event: SimulationEvent) => void,
+  batch_size: number = 100
+): Promise<AggregatedResults> {
+  if (!Number.isInteger(batch_size) || batch_size < 1) {
+    throw new SimulationError('invalid_parameter', 'Batch size must be a positive integer');
+  }
+
+  let batch: SimulationResult[] = [];
+  const aggregated = await runStatisticalSimulation(params, {
+    onResult: (result) => {
+      batch.push(result);
+      if (batch.length >= batch_size) {
+        onEvent({ type: 'results', results: batch });
+        batch = [];
+      }
+    },
+    onProgress: ({ completed, total }) => onEvent({ type: 'progress', completed, total })
+  });
+
+  if (batch.length > 0) {
+    onEvent({ type: 'results', results: batch });
+  }
+  onEvent({ type: 'done', aggregated });
+
+  return aggregated;
+}
+
+// Leanest output for plotting: just the two per-simulation series, in simulation order. The
+// same per-index draws as a full run with the same seed, but nothing is aggregated: no result
+// rows, summaries or companion runs (self_calibrate only moves significance, so it is skipped)
+export async function runStatisticalSimulationArrays(
+  params: SimulationParams
+): Promise<{ p_values: Float64Array; effect_sizes: Float64Array }> {
+  const setup = prepareIterationSetup(params);
+  const p_values = new Float64Array(params.num_simulations);
+  const effect_sizes = new Float64Array(params.num_simulations);
+
+  await runIterations(setup, ({ result }, index) => {
+    p_values[index] = result.p_value;
+    effect_sizes[index] = result.effect_size;
+  });
+
+  return { p_values, effect_sizes };
+}
+
+// Callbacks observed while a simulation runs
+export interface SimulationHooks {
+  onResult?: (result: SimulationResult, index: number) => void;
+  onProgress?: (progress: { completed: number; total: number }) => void;
+  // Checked before each simulation is folded in; aborting ends the run with a 'cancelled' error
+  signal?: AbortSignal;
+}
+
+// Power at every (per-group n, true d) combination: the data behind a power heatmap. Every cell
+// runs from the same seed so neighbouring cells share sampling noise and the surface is smooth.
+// parallel_workers in base_params spreads whole cells over that many workers; each cell runs on
+// one thread, and cells a worker could not take run serially with identical results
+export async function runPowerGrid(
+  base_params: SimulationParams,
+  sample_sizes: number[],
+  effect_sizes: number[]
+): Promise<PowerGridCell[]> {
+  if (sample_sizes.length === 0 || effect_sizes.length === 0) {
+    throw new SimulationError('invalid_parameter', 'The grid needs at least one sample size and one effect size');
+  }
+  if (sample_sizes.some(n => !Number.isInteger(n) || n < 2)) {
+    throw new SimulationError('invalid_parameter', 'Sample sizes must be integers of at least 2');
+  }
+  if (!effect_sizes.every(Number.isFinite)) {
+    throw new SimulationError('invalid_parameter', 'Effect sizes must be finite numbers');
+  }
+
+  const parallel_workers = base_params.parallel_workers ?? 1;
+  if (!Number.isInteger(parallel_workers) || parallel_workers < 1) {
+    throw new SimulationError('invalid_parameter', 'Number of parallel workers must be a positive integer');
+  }
+
+  const seed = base_params.seed ?? Math.floor(Math.random() * 4294967296);
+  const grid = sample_sizes.flatMap(n => effect_sizes.map(effect => ({ n, effect })));
+  const param_sets = grid.map(({ n, effect }): SimulationParams => ({
+    ...base_params,
+    sample_size_per_group: n,
+    group2_sample_size: undefined,
+    effect_prior: { type: 'point', value: effect },
+    null_fraction: undefined,
+    store_individual_results: false,
+    parallel_workers: 1,
+    seed
+  }));
+
+  const results: Array<AggregatedResults | undefined> = new Array(param_sets.length);
+  const num_workers = Math.min(parallel_workers, param_sets.length);
+  if (num_workers > 1 && typeof Worker !== 'undefined') {
+    try {
+      await runSimulationsInWorkers(param_sets, num_workers, results);
+    } catch (error) {
+      if (!(error instanceof WorkerUnavailableError)) throw error;
+    }
+  }
+  for (let k = 0; k < param_sets.length; k++) {
+    results[k] ??= await runStatisticalSimulation(param_sets[k]);
+  }
+
+  return grid.map(({ n, effect }, k) => ({ n, effect, power: results[k]!.power }));
+}
+
+// Power curve over per-group sample sizes: the full simulation at each n (equal groups). The
+// base seed, when given, is reused at every n, so the sweep reproduces and the curve is smooth
+export async function runSampleSizeSweep(
+  base_params: SimulationParams,
+  sample_sizes: number[]
+): Promise<SampleSizePoint[]> {
+  if (sample_sizes.length === 0) {
+    throw new SimulationError('invalid_parameter', 'At least one sample size is required');
+  }
+  if (sample_sizes.some(n => !Number.isInteger(n) || n < 2)) {
+    throw new SimulationError('invalid_parameter', 'Sample sizes must be integers of at least 2');
+  }
+
+  const points: SampleSizePoint[] = [];
+  for (const n of sample_sizes) {
+    const results = await runStatisticalSimulation({
+      ...base_params,
+      sample_size_per_group: n,
+      group2_sample_size: undefined,
+      store_individual_results: false
+    });
+
+    points.push({
+      n,
+      power: results.power,
+      power_ci: results.power_ci,
+      mean_effect_size: results.mean_effect_size
+    });
+  }
+
+  return points;
+}
+
+// Hold total N fixed and sweep the fraction allocated to group 1, running the full
+// simulation at each split to locate the power-optimal allocation
+export async function runAllocationSweep(
+  base_params: SimulationParams,
+  total_n: number,
+  allocation_fractions: number[] = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9]
+): Promise<AllocationPoint[]> {
+  if (!Number.isInteger(total_n) || total_n < 4) {
+    throw new SimulationError('invalid_parameter', 'Total sample size must be an integer of at least 4');
+  }
+  if (allocation_fractions.some(f => !(f > 0 && f < 1))) {
+    throw new SimulationError('invalid_parameter', 'Allocation fractions must lie strictly between 0 and 1');
+  }
+
+  const points: AllocationPoint[] = [];
+  for (const allocation_fraction of allocation_fractions) {
+    const n1 = Math.round(total_n * allocation_fraction);
+    const n2 = total_n - n1;
+    // Splits that leave a group too small for a t-test are skipped
+    if (n1 < 2 || n2 < 2) continue;
+
+    const results = await runStatisticalSimulation({
+      ...base_params,
+      sample_size_per_group: n1,
+      group2_sample_size: n2
+    });
+
+    points.push({
+      allocation_fraction,
+      n1,
+      n2,
+      power: results.significant_count / results.total_count
+    });
+  }
+
+  return points;
+}
+
+// Teaching/regression diagnostic: simulate under the null (group 1 mean forced to group 2's)
+// and measure the Type I error of the Welch t statistic referred to each forced df, alongside
+// the per-sample Satterthwaite df. Wrong df choices show up as miscalibration against alpha.
+export async function coverageVsDF(params: SimulationParams, df_values: number[]): Promise<DFCalibration> {
+  if (df_values.some(df => !(df > 0))) {
+    throw new SimulationError('invalid_parameter', 'Degrees of freedom must be positive');
+  }
+
+  const n1 = params.sample_size_per_group;
+  const n2 = params.group2_sample_size ?? n1;
+  const random = createRandom(params.seed);
+  const rejections = new Array(df_values.length).fill(0);
+  let welch_rejections = 0;
+  const group1_mean = nullGroup1Mean(params);
+  const population_mean = StatisticalUtils.populationMoments(
+    params.group2_mean, params.group2_std, params.group2_distribution).mean;
+
+  for (let i = 0; i < params.num_simulations; i++) {
+    const group1 = StatisticalUtils.contaminateSamples(
+      StatisticalUtils.generateSamples(n1, group1_mean, params.group1_std, params.group1_distribution, random),
+      population_mean, params.group1_contamination, random);
+    const group2 = StatisticalUtils.contaminateSamples(
+      StatisticalUtils.generateSamples(n2, params.group2_mean, params.group2_std, params.group2_distribution, random),
+      population_mean, params.group2_contamination, random);
+
+    const var1 = (jStat as any).variance(group1, true);
+    const var2 = (jStat as any).variance(group2, true);
+    const t = ((jStat as any).mean(group1) - (jStat as any).mean(group2)) / Math.sqrt(var1 / n1 + var2 / n2);
+    const p_for = (df: number) => 2 * (1 - (jStat as any).studentt.cdf(Math.abs(t), df));
+
+    df_values.forEach((df, j) => {
+      if (p_for(df) < params.alpha_level) rejections[j]++;
+    });
+    if (p_for(StatisticalUtils.welchSatterthwaiteDF(var1, n1, var2, n2)) < params.alpha_level) {
+      welch_rejections++;
+    }
+
+    if (i % 100 === 0 && i > 0) {
+      await new Promise(resolve => setTimeout(resolve, 0));
+    }
+  }
+
+  return {
+    nominal_alpha: params.alpha_level,
+    forced_df: df_values.map((df, j) => ({
+      df,
+      type1_error_rate: rejections[j] / params.num_simulations
+    })),
+    welch_type1_error_rate: welch_rejections / params.num_simulations
+  };
+}
+
+// Null rejection rates across a grid of alphas from a single run; every alpha reuses the same p-values
+export async function calibrationCurve(params: SimulationParams, nominal_alphas: number[]): Promise<CalibrationPoint[]> {
+  if (nominal_alphas.some(alpha => !(alpha > 0 && alpha < 1))) {
+    throw new SimulationError('invalid_alpha', 'Nominal alphas must be between 0 and 1');
+  }
+
+  const null_results = await runStatisticalSimulation({
+    ...params,
+    group1_mean: nullGroup1Mean(params),
+    effect_prior: undefined,
+    store_individual_results: true,
+    percentile_summary: false
+  });
+  const p_values = null_results.individual_results.map(r => r.p_value);
+
+  return nominal_alphas.map(nominal_alpha => ({
+    nominal_alpha,
+    empirical_alpha: p_values.filter(p => p < nominal_alpha).length / p_values.length
+  }));
+}
+
+// Smallest per-group n at which the pooled t-test reaches target_power, from the analytic
+// noncentral t power. Power grows with n, so the search doubles n and then bisects
+export function requiredSampleSize(
+  effect_size: number,
+  alpha: number,
+  target_power: number,
+  alternative: Alternative = 'two_sided'
+): number {
+  if (!Number.isFinite(effect_size)) {
+    throw new SimulationError('invalid_parameter', 'Effect size must be a finite number');
+  }
+  if (!(alpha > 0 && alpha < 1)) {
+    throw new SimulationError('invalid_alpha', 'Alpha must be between 0 and 1');
+  }
+  if (!(target_power > 0 && target_power < 1)) {
+    throw new SimulationError('invalid_parameter', 'Target power must be between 0 and 1');
+  }
+
+  const powerAt = (n: number) => StatisticalUtils.analyticPower(effect_size, n, alpha, alternative);
+  if (powerAt(2) >= target_power) return 2;
+
+  let low = 2;
+  let high = 4;
+  while (powerAt(high) < target_power) {
+    if (high >= MAX_REQUIRED_SAMPLE_SIZE) {
+      throw new SimulationError('invalid_parameter',
+        `Power ${target_power} is not reached with ${MAX_REQUIRED_SAMPLE_SIZE} per group ` +
+        `for d = ${effect_size}; the effect is too small (or points against the one-sided alternative)`);
+    }
+    low = high;
+    high = Math.min(high * 2, MAX_REQUIRED_SAMPLE_SIZE);
+  }
+  // Invariant: power(low) < target_power <= power(high)
+  while (high - low > 1) {
+    const mid = Math.floor((low + high) / 2);
+    if (powerAt(mid) >= target_power) {
+      high = mid;
+    } else {
+      low = mid;
+    }
+  }
+  return high;
+}
+
+// Precision-vs-n curve without simulation: the analytic effect-size CI width at each
+// per-group sample size (equal groups)
+export function ciWidthCurve(
+  effect_size: number,
+  sample_sizes: number[],
+  confidence_level: number = 0.95
+): CIWidthPoint[] {
+  if (sample_sizes.length === 0) {
+    throw new SimulationError('invalid_parameter', 'At least one sample size is required');
+  }
+  if (sample_sizes.some(n => !Number.isInteger(n) || n < 2)) {
+    throw new SimulationError('invalid_parameter', 'Sample sizes must be integers of at least 2');
+  }
+
+  return sample_sizes.map(n => ({
+    n,
+    expected_width: StatisticalUtils.effectSizeCIWidth(effect_size, n, n, confidence_level)
+  }));
+}
+
+// Power at each alpha recomputed from a finished run's stored p-values (p < alpha), without
+// re-simulating. Significance rules other than the p-value are not reapplied.
+export function powerAcrossAlphas(results: AggregatedResults, alphas: number[]): AlphaPowerPoint[] {
+  if (results.individual_results.length === 0) {
+    throw new SimulationError('invalid_parameter', 'Power across alphas needs stored individual results');
+  }
+  if (alphas.some(alpha => !(alpha > 0 && alpha < 1))) {
+    throw new SimulationError('invalid_alpha', 'Alphas must be between 0 and 1');
+  }
+
+  const p_values = results.individual_results.map(r => r.p_value);
+  return alphas.map(alpha => ({
+    alpha,
+    power: p_values.filter(p => p < alpha).length / p_values.length
+  }));
+}
+
+// Paired analysis of supplied measurements: paired t-test on after - before, the Pearson
+// correlation between the two occasions, and Cohen's d_z (positive when values increase)
+export function analyzePaired(before: number[], after: number[]): PairedAnalysis {
+  if (before.length === 0 || after.length === 0) {
+    throw new SimulationError('empty_group', 'Paired data must not be empty');
+  }
+  if (before.length !== after.length) {
+    throw new SimulationError('invalid_parameter',
+      `Paired data needs equal lengths (got ${before.length} and ${after.length})`);
+  }
+  if (![...before, ...after].every(Number.isFinite)) {
+    throw new SimulationError('invalid_parameter', 'Paired data must be finite numbers');
+  }
+
+  const test = StatisticalUtils.pairedTTest(after, before);
+  return {
+    n: before.length,
+    t_statistic: test.t_statistic,
+    df: test.df,
+    p_value: test.p_value,
+    correlation: (jStat as any).corrcoeff(before, after),
+    dz: test.effect_size,
+    dz_ci: test.confidence_interval
+  };
+}
+
+// Re-run an observed study many times, treating its effect as the truth. A positive se draws
+// each replication's true effect from N(observed_d, se) to propagate the original uncertainty.
+export async function simulateReplications(
+  observed_d: number,
+  se: number,
+  n1: number,
+  n2: number,
+  num_reps: number,
+  alpha: number,
+  seed?: number
+): Promise<ReplicationResults> {
+  if (!Number.isFinite(observed_d)) {
+    throw new SimulationError('invalid_parameter', 'Observed effect size must be a finite number');
+  }
+  if (!(se >= 0)) {
+    throw new SimulationError('invalid_parameter', 'Standard error must be non-negative');
+  }
+  if (!Number.isInteger(n1) || !Number.isInteger(n2) || n1 < 2 || n2 < 2) {
+    throw new SimulationError('empty_group', 'Each group needs at least 2 observations');
+  }
+  if (!Number.isInteger(num_reps) || num_reps < 1) {
+    throw new SimulationError('invalid_parameter', 'Number of replications must be a positive integer');
+  }
+  if (!(alpha > 0 && alpha < 1)) {
+    throw new SimulationError('invalid_alpha', 'Alpha must be between 0 and 1');
+  }
+
+  const random = createRandom(seed);
+  const effect_sizes: number[] = [];
+  let significant_count = 0;
+
+  for (let i = 0; i < num_reps; i++) {
+    const true_d = se > 0 ? StatisticalUtils.normalRandom(observed_d, se, random) : observed_d;
+    const group1 = StatisticalUtils.generateSamples(n1, true_d, 1, undefined, random);
+    const group2 = StatisticalUtils.generateSamples(n2, 0, 1, undefined, random);
+    const { p_value, effect_size } = StatisticalUtils.twoSampleTTest(group1, group2);
+
+    effect_sizes.push(effect_size);
+    if (p_value < alpha) significant_count++;
+
+    if (i % 100 === 0 && i > 0) {
+      await new Promise(resolve => setTimeout(resolve, 0));
+    }
+  }
+
+  const sorted = [...effect_sizes].sort((a, b) => a - b);
+
+  return {
+    replication_power: significant_count / num_reps,
+    mean_effect_size: effect_sizes.reduce((sum, d) => sum + d, 0) / num_reps,
+    effect_size_interval: [StatisticalUtils.percentile(sorted, 0.025), StatisticalUtils.percentile(sorted, 0.975)],
+    effect_size_histogram: StatisticalUtils.createHistogram(effect_sizes),
+    effect_sizes
+  };
+}
+
+// Multi-endpoint analysis: each subject contributes a vector of outcomes with an exchangeable
+// correlation; every endpoint is t-tested at a Bonferroni-corrected alpha
+async function runEndpointAnalysis(params: SimulationParams, random: RandomSource): Promise<EndpointAnalysis> {
+  const k = params.num_endpoints ?? 1;
+  const rho = params.endpoint_correlation ?? 0;
+  const n1 = params.sample_size_per_group;
+  const n2 = params.group2_sample_size ?? n1;
+  const adjusted_alpha = params.alpha_level / k;
+
+  const correlation = Array.from({length: k}, (_, i) =>
+    Array.from({length: k}, (_, j) => (i === j ? 1 : rho)));
+  const cholesky = StatisticalUtils.choleskyDecomposition(correlation);
+
+  const significant_counts = new Array(k).fill(0);
+  let any_significant_count = 0;
+
+  for (let i = 0; i < params.num_simulations; i++) {
+    const draw = (n: number, mean: number, std: number) => Array.from({length: n}, () =>
+      StatisticalUtils.correlatedNormalVector(cholesky, random).map(z => mean + std * z));
+    const subjects1 = draw(n1, params.group1_mean, params.group1_std);
+    const subjects2 = draw(n2, params.group2_mean, params.group2_std);
+
+    let any_significant = false;
+    for (let j = 0; j < k; j++) {
+      const { p_value } = StatisticalUtils.twoSampleTTest(
+        subjects1.map(s => s[j]),
+        subjects2.map(s => s[j])
+      );
+      if (p_value < adjusted_alpha) {
+        significant_counts[j]++;
+        any_significant = true;
+      }
+    }
+    if (any_significant) any_significant_count++;
+
+    if (i % 100 === 0 && i > 0) {
+      await new Promise(resolve => setTimeout(resolve, 0));
+    }
+  }
+
+  return {
+    num_endpoints: k,
+    endpoint_correlation: rho,
+    adjusted_alpha,
+    per_endpoint_power: significant_counts.map(c => c / params.num_simulations),
+    family_wise_error_rate: any_significant_count / params.num_simulations
+  };
+}
+
+// Deep comparison treating numbers bit-for-bit (NaN equals NaN, 0 differs from -0)
+function resultsAreIdentical(a: unknown, b: unknown): boolean {
+  if (typeof a === 'number' && typeof b === 'number') {
+    return Object.is(a, b);
+  }
+  if (Array.isArray(a) && Array.isArray(b)) {
+    return a.length === b.length && a.every((value, i) => resultsAreIdentical(value, b[i]));
   }
+  if (a && b && typeof a === 'object' && typeof b === 'object') {
+    const keysA = Object.keys(a);
+    const keysB = Object.keys(b);
+    return keysA.length === keysB.length &&
+      keysA.every(key => resultsAreIdentical((a as any)[key], (b as any)[key]));
+  }
+  return a === b;
+}
 
-  // Two-sample t-test using jStat
-  static twoSampleTTest(group1: number[], group2: number[]): {
-    t_statistic: number;
-    p_value: number;
-    effect_size: number;
-    confidence_interval: [number, number];
-  } {
-    const n1 = group1.length;
-    const n2 = group2.length;
+// Upper bound on uniforms recorded by debug_rng, keeping metadata small
+const MAX_DEBUG_RNG_DRAWS = 64;
+
+// Population shapes generateSamples can draw from
+export const SUPPORTED_DISTRIBUTIONS: Distribution['type'][] = [
+  'normal', 'mixture', 'laplace', 'lognormal', 'exponential', 'uniform', 'student_t'
+];
+
+// Largest per-group sample size requiredSampleSize will search
+const MAX_REQUIRED_SAMPLE_SIZE = 1000000;
+
+// Upper bound on raw datasets attached via return_sample_datasets
+const MAX_SAMPLE_DATASETS = 20;
+
+// t-tests below this many degrees of freedom trigger a warning in the metadata
+const LOW_DF_WARNING_THRESHOLD = 3;
+
+// Upper bound on the preliminary null run behind self_calibrate
+const MAX_CALIBRATION_SIMULATIONS = 2000;
+
+// Everything one simulation index needs, resolved and validated once per run. Plain data,
+// so it can be posted to a worker and every thread derives the same per-index streams
+export interface IterationSetup {
+  params: SimulationParams;
+  n1: number;
+  n2: number;
+  stream_seed: number | undefined;
+  antithetic: boolean;
+  clustered: boolean;
+  cluster_size: number;
+  icc: number;
+  buffered_sampling: boolean;
+  test_type: TestType;
+  num_permutations: number;
+  t_test_options: TTestOptions;
+  ci_method: CiMethod;
+  ci_confidence_level: number;
+  effect_size_metric: EffectSizeMetric;
+  significance_rule: SignificanceRule;
+  test_alpha: number;
+  population_means: [number, number]; // Means of the populations actually sampled (see populationMoments)
+  population_stds: [number, number];
+  population_std: number;
+  true_effect_size: number;
+  dataset_indices: number[];
+}
 
-    // Calculate means and variances
-    const mean1 = (jStat as any).mean(group1);
-    const mean2 = (jStat as any).mean(group2);
-    const var1 = (jStat as any).variance(group1, true); // Sample variance
-    const var2 = (jStat as any).variance(group2, true); // Sample variance
+// What one simulated study contributes to the aggregates
+export interface IterationRecord {
+  result: SimulationResult;
+  iteration_effect: number; // True standardized effect this iteration was drawn under
+  sample_stds?: [number, number]; // diagnostics only
+  paired_p_values?: { t_test: number; mann_whitney: number }; // compare_tests only
+  samples?: [number[], number[]]; // Only at return_sample_datasets indices
+  capped_resamples?: number; // Bootstrap resamples actually drawn, when the timeout cut them short
+  capped_permutations?: number; // Permutations actually drawn, when the timeout cut them short
+  bootstrap_mc_error?: [number, number]; // Diagnostics with a bootstrap CI: MC error of each endpoint
+  is_null?: boolean; // Mixed mode (null_fraction > 0) only
+  df?: number; // t-test modes only, after any min_df floor
+}
 
-    // Pooled standard deviation (equal variances assumed)
-    const pooled_var = ((n1 - 1) * var1 + (n2 - 1) * var2) / (n1 + n2 - 2);
-    const pooled_std = Math.sqrt(pooled_var);
+// Stream for one simulation index; the odd member of an antithetic pair (2k, 2k + 1) replays
+// its partner's stream with u -> 1 - u, which negates every inverse-CDF deviate
+function iterationStream(setup: IterationSetup, index: number): RandomSource {
+  const base = createIndexedRandom(setup.stream_seed, setup.antithetic ? index - index % 2 : index);
+  if (!(setup.antithetic && index % 2 === 1)) return base;
 
-    // Standard error
-    const se = pooled_std * Math.sqrt(1/n1 + 1/n2);
+  return () => {
+    const u = base();
+    return u === 0 ? 0 : 1 - u; // Keep the [0, 1) range
+  };
+}
 
-    // t-statistic
-    const t_statistic = (mean1 - mean2) / se;
+// One simulated study: draw both groups, run the test and collect what the aggregates need
+function simulateIteration(setup: IterationSetup, index: number, random: RandomSource): IterationRecord {
+  const { params, n1, n2, population_std, cluster_size, icc } = setup;
+  const iteration_start = performance.now();
+  // per_simulation_timeout_ms bounds the resampling (permutations and bootstrap CIs) below
+  const deadline = params.per_simulation_timeout_ms === undefined
+    ? Infinity
+    : iteration_start + params.per_simulation_timeout_ms;
+  let capped_permutations: number | undefined;
+
+  // Mixed mode: a null_fraction share of iterations simulates no effect at all
+  const null_fraction = params.null_fraction ?? 0;
+  const is_null = null_fraction > 0 && random() < null_fraction;
+
+  // With an effect prior, each iteration draws its own true effect (assurance mode). Group 1's
+  // location is set so its population mean lands where the effect puts it; a mixture's
+  // population sits group1_offset away from its location
+  const effect_prior = params.effect_prior;
+  const [population1_mean, population2_mean] = setup.population_means;
+  const group1_offset = population1_mean - params.group1_mean;
+  let iteration_effect = setup.true_effect_size;
+  let iteration_group1_mean = params.group1_mean;
+  if (is_null) {
+    iteration_effect = 0;
+    iteration_group1_mean = population2_mean - group1_offset;
+  } else if (effect_prior) {
+    iteration_effect = effect_prior.type === 'point'
+      ? effect_prior.value
+      : StatisticalUtils.normalRandom(effect_prior.mean, effect_prior.sd, random);
+    iteration_group1_mean = population2_mean + iteration_effect * population_std - group1_offset;
+  }
 
-    // Degrees of freedom
-    const df = n1 + n2 - 2;
+  // Generate samples from the (optionally seeded) random source
+  const group1 = setup.clustered
+    ? StatisticalUtils.generateClusteredSamples(n1, iteration_group1_mean, params.group1_std, cluster_size, icc, random)
+    : StatisticalUtils.contaminateSamples(StatisticalUtils.generateSamples(
+      n1, iteration_group1_mean, params.group1_std, params.group1_distribution, random, setup.buffered_sampling),
+    iteration_group1_mean + group1_offset, params.group1_contamination, random);
+  const group2 = setup.clustered
+    ? StatisticalUtils.generateClusteredSamples(n2, params.group2_mean, params.group2_std, cluster_size, icc, random)
+    : StatisticalUtils.contaminateSamples(StatisticalUtils.generateSamples(
+      n2, params.group2_mean, params.group2_std, params.group2_distribution, random, setup.buffered_sampling),
+    population2_mean, params.group2_contamination, random);
+
+  // compare_tests needs both independent-group tests whichever one drives the results
+  const { test_type } = setup;
+  const mann_whitney = test_type === 'mann_whitney' || params.compare_tests
+    ? StatisticalUtils.mannWhitneyU(group1, group2, setup.t_test_options.alternative, setup.ci_confidence_level)
+    : null;
+  const t_test = (test_type === 'welch' || test_type === 'pooled' || test_type === 'permutation') || params.compare_tests
+    ? StatisticalUtils.twoSampleTTest(group1, group2, setup.t_test_options)
+    : null;
+
+  let test_result: {
+    p_value: number;
+    effect_size: number;
+    hedges_g?: number;
+    confidence_interval: [number, number];
+    t_statistic?: number;
+    df?: number;
+  };
+  switch (test_type) {
+    case 'mann_whitney':
+      // The rank mode reports the rank-biserial correlation. For normal populations its true
+      // value is 2 Phi(d / sqrt(2)) - 1, so coverage and design analysis move to that scale
+      test_result = {
+        p_value: mann_whitney!.p_value,
+        effect_size: mann_whitney!.rank_biserial,
+        confidence_interval: mann_whitney!.rank_biserial_ci
+      };
+      iteration_effect = 2 * (jStat as any).normal.cdf(iteration_effect / Math.SQRT2, 0, 1) - 1;
+      break;
+    case 'paired':
+      // Groups are drawn independently, so the SD of the differences is sqrt(2) times the
+      // average-variance SD and the true d_z is d / sqrt(2)
+      test_result = StatisticalUtils.pairedTTest(group1, group2, setup.t_test_options);
+      iteration_effect = iteration_effect / Math.SQRT2;
+      break;
+    case 'permutation': {
+      // Only the p-value comes from the permutation distribution; d and its CI are the t-test's
+      const permutation = StatisticalUtils.permutationTest(
+        group1, group2, setup.num_permutations, random, setup.t_test_options.alternative, deadline);
+      if (permutation.permutations < setup.num_permutations && !permutation.exact) {
+        capped_permutations = permutation.permutations;
+      }
+      const { p_value } = permutation;
+      test_result = {
+        p_value,
+        effect_size: t_test!.effect_size,
+        hedges_g: t_test!.hedges_g,
+        confidence_interval: t_test!.confidence_interval
+      };
+      break;
+    }
+    default:
+      test_result = t_test!;
+  }
 
-    // Two-tailed p-value using jStat t-distribution
-    const p_value = 2 * (1 - (jStat as any).studentt.cdf(Math.abs(t_statistic), df));
+  // Glass's delta replaces d, so its CI and the true effect move to the control SD's scale
+  if (setup.effect_size_metric === 'glass_delta') {
+    const control_group = params.glass_control_group ?? 2;
+    const glass = StatisticalUtils.glassDelta(group1, group2, control_group, setup.ci_confidence_level);
+    test_result = { ...test_result, ...glass };
+    iteration_effect = iteration_effect * population_std / setup.population_stds[control_group - 1];
+  }
 
-    // Effect size (Cohen's d)
-    const effect_size = (mean1 - mean2) / pooled_std;
+  // Resampling draws from this iteration's generator, so bootstrap CIs reproduce under a seed
+  let capped_resamples: number | undefined;
+  let bootstrap_mc_error: [number, number] | undefined;
+  if (setup.ci_method.type === 'bootstrap_t' || setup.ci_method.type === 'bootstrap_percentile') {
+    const n_boot = setup.ci_method.n_boot ?? DEFAULT_BOOTSTRAP_RESAMPLES;
+    const estimate_mc_error = params.diagnostics ?? false;
+    const bootstrap = setup.ci_method.type === 'bootstrap_t'
+      ? StatisticalUtils.bootstrapTCI(
+        group1, group2, n_boot, setup.ci_confidence_level, random, deadline, estimate_mc_error)
+      : StatisticalUtils.bootstrapPercentileCI(
+        group1, group2, n_boot, setup.ci_confidence_level, random, deadline, estimate_mc_error);
+    test_result.confidence_interval = bootstrap.confidence_interval;
+    if (bootstrap.resamples < n_boot) capped_resamples = bootstrap.resamples;
+    bootstrap_mc_error = bootstrap.endpoint_mc_error;
+  }
 
-    // Confidence interval for effect size using t-distribution
-    const t_critical = (jStat as any).studentt.inv(0.975, df); // 95% CI
-    const ci_margin = t_critical * se / pooled_std;
-    const confidence_interval: [number, number] = [
-      effect_size - ci_margin,
-      effect_size + ci_margin
-    ];
+  // The SE approximation degrades as |d| grows; switch extreme effects to the exact interval,
+  // or use it for every simulation under the noncentral_t method
+  const noncentral_ci = params.noncentral_ci_threshold !== undefined &&
+    Math.abs(test_result.effect_size) > params.noncentral_ci_threshold;
+  if (noncentral_ci || setup.ci_method.type === 'noncentral_t') {
+    test_result.confidence_interval = StatisticalUtils.noncentralTCI(
+      test_result.effect_size, n1, n2, setup.ci_confidence_level);
+  }
 
-    return {
-      t_statistic,
-      p_value: Math.max(0, Math.min(1, p_value)),
-      effect_size,
-      confidence_interval
-    };
+  // Calculate S-value
+  const s_value = StatisticalUtils.calculateSValue(test_result.p_value);
+
+  // Under glass_delta this maps Glass's delta, which equals the probability of superiority
+  // only when the two population SDs are equal
+  const cles = (value: number) => StatisticalUtils.commonLanguageEffectSize(value, test_type);
+
+  // Opt-in (one numerical integral per simulation), independent-groups t modes only. The JZS
+  // factor is defined on the equal-variance t, so Welch runs also use the pooled t implied by
+  // Cohen's d rather than their Welch t; the two coincide when n1 = n2
+  const bayes_factor = params.bayes_factor && t_test &&
+    (test_type === 'pooled' || test_type === 'welch' || test_type === 'permutation')
+    ? StatisticalUtils.jzsBayesFactor(t_test.effect_size * Math.sqrt(n1 * n2 / (n1 + n2)), n1, n2)
+    : undefined;
+
+  // Check significance under the chosen rule
+  const significant = StatisticalUtils.isSignificant(test_result, setup.test_alpha, setup.significance_rule);
+
+  const result: SimulationResult = {
+    p_value: test_result.p_value,
+    effect_size: test_result.effect_size,
+    confidence_interval: test_result.confidence_interval,
+    s_value,
+    significant,
+    cles: cles(test_result.effect_size),
+    cles_ci: [cles(test_result.confidence_interval[0]), cles(test_result.confidence_interval[1])]
+  };
+  if (test_result.hedges_g !== undefined) result.hedges_g = test_result.hedges_g;
+  if (bayes_factor !== undefined) result.bayes_factor = bayes_factor;
+  // Set only where the p-value is the t statistic referred to t(df), so other software can check it
+  if (test_result.t_statistic !== undefined && test_result.df !== undefined) {
+    result.t_statistic = test_result.t_statistic;
+    result.degrees_of_freedom = test_result.df;
+  }
+  if (noncentral_ci) result.noncentral_ci = true;
+
+  const record: IterationRecord = { result, iteration_effect };
+  if (test_result.df !== undefined) record.df = test_result.df;
+  if (null_fraction > 0) record.is_null = is_null;
+  if (capped_resamples !== undefined) record.capped_resamples = capped_resamples;
+  if (capped_permutations !== undefined) record.capped_permutations = capped_permutations;
+  if (bootstrap_mc_error !== undefined) record.bootstrap_mc_error = bootstrap_mc_error;
+  if (params.diagnostics) {
+    record.sample_stds = [(jStat as any).stdev(group1, true), (jStat as any).stdev(group2, true)];
+  }
+  // Both tests see exactly the same samples, so differences reflect the tests alone
+  if (t_test && mann_whitney) {
+    record.paired_p_values = { t_test: t_test.p_value, mann_whitney: mann_whitney.p_value };
   }
+  if (setup.dataset_indices.includes(index)) {
+    record.samples = [group1, group2];
+  }
+  return record;
+}
 
-  // Calculate S-value (Shannon information)
-  static calculateSValue(p_value: number): number {
-    if (p_value <= 0) return Infinity;
-    if (p_value >= 1) return 0;
-    return -Math.log2(p_value);
+// Simulate indices [start, end); the unit of work handed to each parallel worker
+export function simulateRange(setup: IterationSetup, start: number, end: number): IterationRecord[] {
+  const records: IterationRecord[] = [];
+  for (let i = start; i < end; i++) {
+    records.push(simulateIteration(setup, i, iterationStream(setup, i)));
   }
+  return records;
+}
 
-  // Create p-value histogram bins
-  static createPValueHistogram(p_values: number[], alpha: number = 0.05, num_bins: number = 20): Array<{
-    bin_start: number;
-    bin_end: number;
-    count: number;
-    significant: boolean;
-  }> {
-    const histogram = [];
+// Workers could not be created or their script failed to load (as opposed to a simulation
+// error reported by a running worker); the caller falls back to the serial loop
+class WorkerUnavailableError extends Error {}
+
+// Rebuild an error a worker reported, keeping its SimulationError kind when it had one
+function workerError(data: { error: string; kind?: SimulationErrorKind }): Error {
+  return data.kind ? new SimulationError(data.kind, data.error) : new Error(data.error);
+}
 
-    for (let i = 0; i < num_bins; i++) {
-      const bin_start = i / num_bins;
-      const bin_end = (i + 1) / num_bins;
+// One contiguous index chunk per worker. Chunks finish in any order but are consumed in
+// index order, so a seeded parallel run matches the serial loop exactly
+function runChunksInWorkers(
+  setup: IterationSetup,
+  num_workers: number
+): { chunks: Promise<IterationRecord[]>[]; terminate: () => void } {
+  const total = setup.params.num_simulations;
+  const chunk_size = Math.ceil(total / num_workers);
+  const workers: Worker[] = [];
+
+  const chunks = Array.from({length: Math.ceil(total / chunk_size)}, (_, w) => {
+    const start = w * chunk_size;
+    const end = Math.min(total, start + chunk_size);
+    let worker: Worker;
+    try {
+      worker = new Worker(new URL('../workers/simulation-chunk.worker.ts', import.meta.url), { type: 'module' });
+    } catch (error) {
+      workers.forEach(created => created.terminate());
+      throw new WorkerUnavailableError(error instanceof Error ? error.message : 'Simulation worker could not be created');
+    }
+    workers.push(worker);
 
-      let count = 0;
-      for (const p of p_values) {
-        if (i === num_bins - 1) {
-          // Last bin includes 1.0
-          if (p >= bin_start && p <= bin_end) count++;
+    return new Promise<IterationRecord[]>((resolve, reject) => {
+      worker.onmessage = (e: MessageEvent) => {
+        worker.terminate();
+        if (e.data.type === 'SUCCESS') {
+          resolve(e.data.records);
         } else {
-          if (p >= bin_start && p < bin_end) count++;
+          reject(workerError(e.data));
         }
-      }
+      };
+      worker.onerror = (e: ErrorEvent) => {
+        worker.terminate();
+        reject(new WorkerUnavailableError(e.message || 'Simulation worker failed'));
+      };
+      worker.postMessage({ type: 'RANGE', setup, start, end });
+    });
+  });
+  // The caller stops at the first failed chunk; later chunks failing as well must not
+  // surface as unhandled rejections
+  chunks.forEach(chunk => chunk.catch(() => {}));
 
-      histogram.push({
-        bin_start,
-        bin_end,
-        count,
-        significant: bin_end <= alpha
-      });
-    }
+  return { chunks, terminate: () => workers.forEach(worker => worker.terminate()) };
+}
 
-    return histogram;
-  }
+// Whole runs spread over a pool of workers, each taking the next unstarted run as it frees up.
+// Finished runs land in results by index; on failure the rest stay undefined
+function runSimulationsInWorkers(
+  param_sets: SimulationParams[],
+  num_workers: number,
+  results: Array<AggregatedResults | undefined>
+): Promise<void> {
+  return new Promise((resolve, reject) => {
+    const workers: Worker[] = [];
+    let next = 0;
+    let pending = param_sets.length;
+    let failed = false;
+    const fail = (error: Error) => {
+      if (failed) return;
+      failed = true;
+      workers.forEach(worker => worker.terminate());
+      reject(error);
+    };
 
-  // Calculate confidence interval coverage
-  static calculateCICoverage(
-    true_value: number,
-    confidence_intervals: Array<[number, number]>
-  ): number {
-    let coverage_count = 0;
-    for (const [lower, upper] of confidence_intervals) {
-      if (true_value >= lower && true_value <= upper) {
-        coverage_count++;
+    const dispatch = (worker: Worker) => {
+      if (next >= param_sets.length) {
+        worker.terminate();
+        return;
+      }
+      const index = next++;
+      worker.onmessage = (e: MessageEvent) => {
+        if (e.data.type !== 'SUCCESS') {
+          fail(workerError(e.data));
+          return;
+        }
+        results[index] = e.data.results;
+        if (--pending === 0) resolve();
+        dispatch(worker);
+      };
+      worker.postMessage({ type: 'RUN', params: param_sets[index] });
+    };
+
+    for (let w = 0; w < num_workers && !failed; w++) {
+      let worker: Worker;
+      try {
+        worker = new Worker(new URL('../workers/simulation-chunk.worker.ts', import.meta.url), { type: 'module' });
+      } catch (error) {
+        fail(new WorkerUnavailableError(error instanceof Error ? error.message : 'Simulation worker could not be created'));
+        return;
       }
+      workers.push(worker);
+      worker.onerror = (e: ErrorEvent) => fail(new WorkerUnavailableError(e.message || 'Simulation worker failed'));
+      dispatch(worker);
     }
-    return coverage_count / confidence_intervals.length;
-  }
+  });
+}
 
-  // Calculate mean confidence interval width
-  static calculateMeanCIWidth(confidence_intervals: Array<[number, number]>): number {
-    const widths = confidence_intervals.map(([lower, upper]) => upper - lower);
-    return jStat.mean(widths);
-  }
+// Group 1 location that gives both sampled populations the same mean: group 2's mean itself,
+// unless a mixture offsets a population from its location
+function nullGroup1Mean(params: SimulationParams): number {
+  const population1 = StatisticalUtils.populationMoments(params.group1_mean, params.group1_std, params.group1_distribution);
+  const population2 = StatisticalUtils.populationMoments(params.group2_mean, params.group2_std, params.group2_distribution);
+  return population2.mean - (population1.mean - params.group1_mean);
 }
 
-// Production-ready simulation function using jStat
-async function runStatisticalSimulation(params: any): Promise<any> {
+// The same design with the means equalized and the extra outputs off, on its own seed stream
+// so its draws never repeat the main run's
+function nullRunParams(params: SimulationParams, num_simulations: number, stream: number): SimulationParams {
+  return {
+    ...params,
+    group1_mean: nullGroup1Mean(params),
+    num_simulations,
+    seed: params.seed === undefined ? undefined : deriveSeed(params.seed, 0, stream),
+    self_calibrate: false,
+    include_null_calibration: false,
+    effect_prior: undefined,
+    null_fraction: undefined,
+    percentile_summary: false,
+    return_sample_datasets: 0,
+    scatter_sample_size: 0,
+    power_trace: false,
+    num_endpoints: 1,
+    debug_rng: false,
+    diagnostics: false,
+    compare_tests: false,
+    bayes_factor: false,
+    rope: undefined
+  };
+}
+
+// Validate a run's parameters and resolve everything simulateIteration needs. Checks that only
+// concern the aggregates stay in executeSimulation; test_alpha starts at alpha_level and is
+// replaced there when self_calibrate asks for it
+function prepareIterationSetup(params: SimulationParams): IterationSetup {
   const {
     group1_mean,
     group1_std,
@@ -158,43 +2593,219 @@ async function runStatisticalSimulation(params: any): Promise<any> {
     alpha_level
   } = params;
 
-  const results = [];
-  const p_values = [];
-  const effect_sizes = [];
-  const confidence_intervals = [];
+  const n1 = sample_size_per_group;
+  const n2 = params.group2_sample_size ?? sample_size_per_group;
+  if (![n1, n2].every(n => Number.isInteger(n) && n >= 2)) {
+    throw new SimulationError('empty_group', 'Each group needs an integer sample size of at least 2');
+  }
+  if (!(group1_std > 0) || !(group2_std > 0) || !Number.isFinite(group1_std) || !Number.isFinite(group2_std)) {
+    throw new SimulationError('invalid_std', 'Standard deviations must be positive');
+  }
+  if (!(alpha_level > 0 && alpha_level < 1)) {
+    throw new SimulationError('invalid_alpha', 'Alpha must be between 0 and 1');
+  }
+  // Each simulation index gets its own counter-derived stream, so a seeded run reproduces
+  // exactly regardless of how (or in what order) iterations are executed.
+  // Unseeded antithetic runs still need pairs to share a stream, so draw a base seed once
+  const antithetic = params.antithetic_sampling ?? false;
+  const stream_seed = params.seed ?? (antithetic ? Math.floor(Math.random() * 4294967296) : undefined);
+  const significance_rule: SignificanceRule = params.significance_rule ?? { type: 'p_value' };
+
+  StatisticalUtils.validateDistribution(params.group1_distribution);
+  StatisticalUtils.validateDistribution(params.group2_distribution);
+  StatisticalUtils.validateContamination(params.group1_contamination, 1);
+  StatisticalUtils.validateContamination(params.group2_contamination, 2);
+  const contaminated = [params.group1_contamination, params.group2_contamination]
+    .some(c => c !== undefined && c.fraction > 0);
+
+  if (params.noncentral_ci_threshold !== undefined && !(params.noncentral_ci_threshold >= 0)) {
+    throw new SimulationError('invalid_parameter', 'Noncentral CI threshold must be non-negative');
+  }
+
+  // Cluster sampling (normal populations only); icc = 0 falls back to independent draws
+  const cluster_size = params.cluster_size ?? 1;
+  const icc = params.icc ?? 0;
+  if (!Number.isInteger(cluster_size) || cluster_size < 1) {
+    throw new SimulationError('invalid_parameter', 'Cluster size must be a positive integer');
+  }
+  if (!(icc >= 0 && icc < 1)) {
+    throw new SimulationError('invalid_parameter', 'Intraclass correlation must be in [0, 1)');
+  }
+  const clustered = cluster_size > 1 && icc > 0;
+  if (clustered && [params.group1_distribution, params.group2_distribution]
+    .some(d => d !== undefined && d.type !== 'normal')) {
+    throw new SimulationError('invalid_parameter', 'Cluster sampling is only available for normal populations');
+  }
+  if (clustered && contaminated) {
+    throw new SimulationError('invalid_parameter', 'Cluster sampling cannot be combined with contaminated populations');
+  }
+
+  // Mirroring only yields negated deviates for inverse-CDF draws, so force the buffered path
+  if (antithetic && (clustered || contaminated || [params.group1_distribution, params.group2_distribution]
+    .some(d => d !== undefined && d.type === 'mixture'))) {
+    throw new SimulationError('invalid_parameter', 'Antithetic sampling cannot be combined with clustered, mixture or contaminated populations');
+  }
+  // The null_fraction coin and a normal effect prior draw from the same stream, but u -> 1 - u
+  // does not mirror them, so a pair would no longer share its design
+  if (antithetic && ((params.null_fraction ?? 0) > 0 || params.effect_prior?.type === 'normal')) {
+    throw new SimulationError('invalid_parameter', 'Antithetic sampling cannot be combined with null_fraction or a normal effect prior');
+  }
+  // A mirrored pair has t and -t, so a two-sided test rejects both or neither and the variance
+  // grows; one-sided decisions are monotone in t, which is where the pairing pays off
+  if (antithetic && (params.alternative ?? 'two_sided') === 'two_sided') {
+    throw new SimulationError('invalid_parameter', 'Antithetic sampling needs a one-sided alternative');
+  }
+  const buffered_sampling = params.buffered_sampling || antithetic;
+
+  const test_type = params.test_type ?? 'pooled';
+  // Level of every per-simulation effect-size CI; CI-based significance rules and coverage follow it
+  const ci_confidence_level = params.ci_confidence_level ?? 0.95;
+  if (!(ci_confidence_level > 0 && ci_confidence_level < 1)) {
+    throw new SimulationError('invalid_alpha', 'CI confidence level must be between 0 and 1');
+  }
+  const t_test_options: TTestOptions = {
+    variance_denominator: params.variance_denominator,
+    variance_assumption: test_type === 'welch' ? 'welch' : 'pooled',
+    min_df: params.min_df,
+    alternative: params.alternative,
+    confidence_level: ci_confidence_level
+  };
+  // The effect-size CIs stay two-sided, so CI-based decisions only match a two-sided test
+  if ((params.alternative ?? 'two_sided') !== 'two_sided' &&
+    (significance_rule.type === 'ci_excludes_null' || significance_rule.type === 'ci_excludes_null_and_sesoi')) {
+    throw new SimulationError('invalid_parameter', 'CI-based significance rules require a two-sided alternative');
+  }
+  if (params.min_df !== undefined && !(params.min_df > 0)) {
+    throw new SimulationError('invalid_parameter', 'Minimum degrees of freedom must be positive');
+  }
+
+  const num_permutations = params.num_permutations ?? DEFAULT_NUM_PERMUTATIONS;
+  if (test_type === 'permutation' && !(Number.isInteger(num_permutations) && num_permutations >= 1)) {
+    throw new SimulationError('invalid_parameter', 'Number of permutations must be a positive integer');
+  }
+
+  const ci_method: CiMethod = params.ci_method ?? { type: 'analytic' };
+  if ('n_boot' in ci_method && ci_method.n_boot !== undefined &&
+    !(Number.isInteger(ci_method.n_boot) && ci_method.n_boot >= 1)) {
+    throw new SimulationError('invalid_parameter', 'Number of bootstrap resamples must be a positive integer');
+  }
+
+  if ('sesoi' in significance_rule && !(significance_rule.sesoi >= 0)) {
+    throw new SimulationError('invalid_parameter', 'Smallest effect size of interest must be non-negative');
+  }
+
+  // Resampled and noncentral intervals are built for independent-groups Cohen's d
+  if ((test_type === 'mann_whitney' || test_type === 'paired') &&
+    (ci_method.type !== 'analytic' || params.noncentral_ci_threshold !== undefined)) {
+    throw new SimulationError('invalid_parameter', `The ${test_type === 'paired' ? 'paired t' : 'Mann-Whitney'} test only supports its analytic confidence interval`);
+  }
+  if (test_type === 'paired' && n1 !== n2) {
+    throw new SimulationError('invalid_parameter', `The paired t-test needs equal group sizes (got ${n1} and ${n2})`);
+  }
+  const effect_size_metric: EffectSizeMetric = params.effect_size_metric ?? 'cohens_d';
+  if (effect_size_metric === 'hedges_g' && test_type === 'mann_whitney') {
+    throw new SimulationError('invalid_parameter', "Hedges' g is not defined for the Mann-Whitney test");
+  }
+  if (effect_size_metric === 'glass_delta') {
+    if (test_type === 'mann_whitney' || test_type === 'paired') {
+      throw new SimulationError('invalid_parameter', "Glass's delta needs an independent-groups t-test mode");
+    }
+    if (ci_method.type !== 'analytic' || params.noncentral_ci_threshold !== undefined) {
+      throw new SimulationError('invalid_parameter', "Glass's delta only supports its analytic confidence interval");
+    }
+  }
 
-  // True effect size for coverage calculation
-  const true_effect_size = (group1_mean - group2_mean) /
-    Math.sqrt((group1_std ** 2 + group2_std ** 2) / 2);
+  // Raw datasets kept for inspection: evenly spaced simulation indices, so the choice is
+  // deterministic and reproduces exactly under the same seed
+  const dataset_count = Math.min(params.return_sample_datasets ?? 0, MAX_SAMPLE_DATASETS, num_simulations);
+  const dataset_indices = Array.from({length: dataset_count}, (_, k) => Math.floor(k * num_simulations / dataset_count));
 
-  for (let i = 0; i < num_simulations; i++) {
-    // Generate samples using jStat
-    const group1 = Array.from({length: sample_size_per_group},
-      () => StatisticalUtils.normalRandom(group1_mean, group1_std));
-    const group2 = Array.from({length: sample_size_per_group},
-      () => StatisticalUtils.normalRandom(group2_mean, group2_std));
+  // True effect size for coverage calculation, from the populations actually sampled
+  const population1 = StatisticalUtils.populationMoments(group1_mean, group1_std, params.group1_distribution);
+  const population2 = StatisticalUtils.populationMoments(group2_mean, group2_std, params.group2_distribution);
+  const population_std = Math.sqrt((population1.std ** 2 + population2.std ** 2) / 2);
+  const true_effect_size = (population1.mean - population2.mean) / population_std;
 
-    // Perform proper t-test using jStat
-    const test_result = StatisticalUtils.twoSampleTTest(group1, group2);
+  const effect_prior = params.effect_prior;
+  if (effect_prior?.type === 'normal' && !(effect_prior.sd >= 0)) {
+    throw new SimulationError('invalid_parameter', 'Effect prior standard deviation must be non-negative');
+  }
 
-    // Calculate S-value
-    const s_value = StatisticalUtils.calculateSValue(test_result.p_value);
+  // Timeouts trade reproducibility for bounded runtime: where the cap bites depends on the machine
+  if (params.per_simulation_timeout_ms !== undefined && !(params.per_simulation_timeout_ms > 0)) {
+    throw new SimulationError('invalid_parameter', 'Per-simulation timeout must be positive');
+  }
 
-    // Check significance
-    const significant = test_result.p_value < alpha_level;
+  const null_fraction = params.null_fraction ?? 0;
+  if (!(null_fraction >= 0 && null_fraction <= 1)) {
+    throw new SimulationError('invalid_parameter', 'Null fraction must be between 0 and 1');
+  }
 
-    const result = {
-      p_value: test_result.p_value,
-      effect_size: test_result.effect_size,
-      confidence_interval: test_result.confidence_interval,
-      s_value,
-      significant
-    };
+  const parallel_workers = params.parallel_workers ?? 1;
+  if (!Number.isInteger(parallel_workers) || parallel_workers < 1) {
+    throw new SimulationError('invalid_parameter', 'Number of parallel workers must be a positive integer');
+  }
+
+  return {
+    params,
+    n1,
+    n2,
+    stream_seed,
+    antithetic,
+    clustered,
+    cluster_size,
+    icc,
+    buffered_sampling,
+    test_type,
+    num_permutations,
+    t_test_options,
+    ci_confidence_level,
+    ci_method,
+    effect_size_metric,
+    significance_rule,
+    test_alpha: alpha_level,
+    population_means: [population1.mean, population2.mean],
+    population_stds: [population1.std, population2.std],
+    population_std,
+    true_effect_size,
+    dataset_indices
+  };
+}
 
-    results.push(result);
-    p_values.push(test_result.p_value);
-    effect_sizes.push(test_result.effect_size);
-    confidence_intervals.push(test_result.confidence_interval);
+// Simulate every index in order and hand each record to visit. Worker threads take over the
+// simulation itself when parallel_workers asks for them; visiting stays on this thread. The
+// serial loop remains for draw capture (debug_rng) and non-browser contexts. Returns a warning
+// when workers were requested but unavailable
+async function runIterations(
+  setup: IterationSetup,
+  visit: (record: IterationRecord, index: number) => void,
+  captureRandom?: (index: number) => RandomSource
+): Promise<string | null> {
+  const num_simulations = setup.params.num_simulations;
+  const num_workers = Math.min(setup.params.parallel_workers ?? 1, num_simulations);
+  // Every index has its own random stream, so when workers turn out to be unavailable the
+  // serial loop picks up at the first unvisited index and the results stay bit-identical
+  let next_index = 0;
+  let worker_fallback: string | null = null;
+  if (num_workers > 1 && !captureRandom && typeof Worker !== 'undefined') {
+    let terminate = () => {};
+    try {
+      const run = runChunksInWorkers(setup, num_workers);
+      terminate = run.terminate;
+      for (const chunk of run.chunks) {
+        for (const record of await chunk) {
+          visit(record, next_index++);
+        }
+      }
+    } catch (error) {
+      if (!(error instanceof WorkerUnavailableError)) throw error;
+      worker_fallback = error.message;
+    } finally {
+      terminate();
+    }
+  }
+  for (let i = next_index; i < num_simulations; i++) {
+    visit(simulateIteration(setup, i, captureRandom ? captureRandom(i) : iterationStream(setup, i)), i);
 
     // Yield control occasionally to prevent UI blocking
     if (i % 100 === 0 && i > 0) {
@@ -202,34 +2813,602 @@ async function runStatisticalSimulation(params: any): Promise<any> {
     }
   }
 
-  // Calculate aggregated statistics using jStat
-  const significant_count = results.filter(r => r.significant).length;
-  const mean_effect_size = (jStat as any).mean(effect_sizes);
-  const mean_ci_width = StatisticalUtils.calculateMeanCIWidth(confidence_intervals);
-  const ci_coverage = StatisticalUtils.calculateCICoverage(true_effect_size, confidence_intervals);
-
-  // Calculate effect size CI using jStat
-  const sorted_effect_sizes = [...effect_sizes].sort((a, b) => a - b);
-  const lower_idx = Math.floor(0.025 * sorted_effect_sizes.length);
-  const upper_idx = Math.floor(0.975 * sorted_effect_sizes.length);
-  const effect_size_ci: [number, number] = [
-    sorted_effect_sizes[lower_idx],
-    sorted_effect_sizes[Math.min(upper_idx, sorted_effect_sizes.length - 1)]
-  ];
-
-  // Create histogram using jStat-based function
-  const p_value_histogram = StatisticalUtils.createPValueHistogram(p_values, alpha_level, 20);
+  return worker_fallback === null ? null
+    : `Parallel workers were unavailable (${worker_fallback}); simulations from index ${next_index} ran serially`;
+}
 
-  return {
+async function executeSimulation(params: SimulationParams, hooks: SimulationHooks = {}): Promise<AggregatedResults> {
+  const { num_simulations, alpha_level } = params;
+  const setup = prepareIterationSetup(params);
+  const {
+    n1,
+    n2,
+    antithetic,
+    test_type,
+    num_permutations,
+    ci_confidence_level,
+    effect_size_metric
+  } = setup;
+  const design_effect = StatisticalUtils.designEffect(setup.cluster_size, setup.icc);
+
+  const rng_draws: number[] | null = params.debug_rng ? [] : null;
+  const rng_draw_limit = Math.min(params.debug_rng_draws ?? 16, MAX_DEBUG_RNG_DRAWS);
+
+  const num_endpoints = params.num_endpoints ?? 1;
+  if (!Number.isInteger(num_endpoints) || num_endpoints < 1) {
+    throw new SimulationError('invalid_parameter', 'Number of endpoints must be a positive integer');
+  }
+  const endpoint_correlation = params.endpoint_correlation ?? 0;
+  if (num_endpoints > 1 && !(endpoint_correlation > -1 / (num_endpoints - 1) && endpoint_correlation < 1)) {
+    throw new SimulationError('invalid_parameter', `Endpoint correlation must lie in (${(-1 / (num_endpoints - 1)).toFixed(3)}, 1) for ${num_endpoints} endpoints`);
+  }
+
+  const effect_size_thresholds = params.effect_size_thresholds ?? [0.2, 0.5, 0.8];
+  if (!(effect_size_thresholds[0] >= 0 && effect_size_thresholds[0] < effect_size_thresholds[1] &&
+    effect_size_thresholds[1] < effect_size_thresholds[2])) {
+    throw new SimulationError('invalid_parameter', 'Effect size thresholds must be non-negative and strictly increasing');
+  }
+  const effect_size_class_counts: Record<EffectSizeClass, number> = { negligible: 0, small: 0, medium: 0, large: 0 };
+  // Cohen's cut-offs are on the d scale; the Mann-Whitney rank-biserial r is only banded
+  // against thresholds given explicitly for it
+  const classify_effect_sizes = test_type !== 'mann_whitney' || params.effect_size_thresholds !== undefined;
+
+  // Design analysis (Type M/S) conditions on significance against each iteration's true effect
+  let exaggeration_sum = 0;
+  let sign_error_count = 0;
+  let design_analysis_count = 0;
+
+  let low_df_count = 0;
+
+  // Reservoir sample of (effect_size, p_value) points for scatter plots, drawn from its own
+  // seed stream so the subsample reproduces without disturbing the simulation draws
+  const scatter_sample_size = params.scatter_sample_size ?? 0;
+  if (!Number.isInteger(scatter_sample_size) || scatter_sample_size < 0) {
+    throw new SimulationError('invalid_parameter', 'Scatter sample size must be a non-negative integer');
+  }
+  const scatter_random = createIndexedRandom(params.seed, 0, 4);
+  const scatter_sample: Array<[number, number]> = [];
+
+  // Effect sizes accumulated within p-value strata (winner's curse breakdown)
+  const strata_edges = params.pvalue_strata ?? [0, 0.01, 0.05, 1];
+  if (strata_edges.length < 2 || strata_edges.some((edge, k) =>
+    edge < 0 || edge > 1 || (k > 0 && edge <= strata_edges[k - 1]))) {
+    throw new SimulationError('invalid_parameter', 'p-value strata must be at least two increasing edges within [0, 1]');
+  }
+  const strata = strata_edges.slice(0, -1).map((p_low, k) => ({
+    p_low,
+    p_high: strata_edges[k + 1],
+    effect_sum: 0,
+    abs_effect_sum: 0,
+    count: 0
+  }));
+
+  const rope = params.rope;
+  if (rope && !(rope[0] < rope[1])) {
+    throw new SimulationError('invalid_parameter', 'ROPE lower bound must be below its upper bound');
+  }
+  let rope_accept_count = 0;
+  let rope_reject_count = 0;
+
+  const histogram_bins = params.histogram_bins ?? 20;
+  if (!Number.isInteger(histogram_bins) || histogram_bins < 1) {
+    throw new SimulationError('invalid_parameter', 'Number of histogram bins must be a positive integer');
+  }
+
+  const storey_lambda = params.storey_lambda ?? 0.5;
+  if (!(storey_lambda >= 0 && storey_lambda < 1)) {
+    throw new SimulationError('invalid_parameter', 'Storey lambda must be in [0, 1)');
+  }
+
+  // Self-calibration: a smaller null run of the same design (own seed stream, extras off)
+  // measures the actual size; calibrate_alpha then tests at the null p-value quantile that
+  // rejects alpha_level of null datasets, trading nominal for empirical size control
+  let size_calibration: SizeCalibration | undefined;
+  if (params.self_calibrate) {
+    const calibration_simulations = Math.min(num_simulations, MAX_CALIBRATION_SIMULATIONS);
+    const null_run = await executeSimulation({
+      ...nullRunParams(params, calibration_simulations, 2),
+      store_individual_results: true
+    });
+    const null_p_values = null_run.individual_results.map(r => r.p_value).sort((a, b) => a - b);
+
+    size_calibration = {
+      nominal_alpha: alpha_level,
+      estimated_size: null_p_values.filter(p => p < alpha_level).length / calibration_simulations,
+      calibration_simulations,
+      calibrated_alpha: params.calibrate_alpha
+        ? StatisticalUtils.percentile(null_p_values, alpha_level)
+        : null
+    };
+    setup.test_alpha = size_calibration.calibrated_alpha ?? alpha_level;
+  }
+
+  // Without retained rows nothing grows with num_simulations: counts and sums are kept
+  // incrementally, and quantiles and data-ranged histograms come from bounded-memory t-digests
+  const store_individual_results = params.store_individual_results ?? true;
+  const effect_size_digest = new TDigest();
+  const cles_digest = new TDigest();
+  const p_value_digest = new TDigest();
+  const ci_width_digest = new TDigest();
+
+  const results: SimulationResult[] = [];
+  const p_values: number[] = [];
+  const effect_sizes: number[] = [];
+  const cles_values: number[] = [];
+  const ci_widths: number[] = [];
+  let significant_count = 0;
+  // p-value bin edges are fixed up front, so the histograms are counted as results arrive
+  const multiple_comparison = params.multiple_comparison ?? 'none';
+  const p_value_edges = StatisticalUtils.pValueHistogramEdges(
+    alpha_level, histogram_bins, params.histogram_align_to_alpha);
+  const p_value_bin_counts = new Array<number>(p_value_edges.length - 1).fill(0);
+  let p_value_count_above_lambda = 0;
+  let unadjusted_significant_count = 0;
+  let bonferroni_significant_count = 0;
+  let p_value_running_mean = 0;
+  let p_value_m2 = 0;
+  let ci_width_sum = 0;
+  let effect_size_sum = 0;
+  // Welford running moments, so the spread needs no retained rows
+  let effect_size_running_mean = 0;
+  let effect_size_m2 = 0;
+  let cles_sum = 0;
+  // Fixed-effect pooling of Hedges' g, independent-groups t modes only. Every simulation shares
+  // n1 and n2, so the weights taken at the common g are equal; weighting by each simulation's
+  // own g-dependent variance would down-weight large estimates and bias the pool toward zero
+  const pool_hedges_g = test_type !== 'mann_whitney' && test_type !== 'paired' && n1 + n2 - 2 > 2;
+  let hedges_g_sum = 0;
+  let hedges_g_count = 0;
+  let noncentral_ci_switches = 0;
+  const group1_stds: number[] = [];
+  const group2_stds: number[] = [];
+  const group1_std_digest = new TDigest();
+  const group2_std_digest = new TDigest();
+  let bootstrap_mc_error_count = 0;
+  let bootstrap_mc_finite_count = 0;
+  let bootstrap_mc_lower_sum = 0;
+  let bootstrap_mc_upper_sum = 0;
+
+  const dataset_count = setup.dataset_indices.length;
+  const sample_datasets: SampleDataset[] = [];
+
+  const effect_prior = params.effect_prior;
+  let covered_count = 0;
+  let not_covered_count = 0;
+
+  const p_value_differences: number[] = [];
+  const p_value_difference_digest = new TDigest();
+  let p_value_difference_sum = 0;
+  let test_disagreement_count = 0;
+
+  let capped_simulations = 0;
+  let capped_resamples_sum = 0;
+  let capped_permutation_simulations = 0;
+  let capped_permutations_sum = 0;
+
+  const null_fraction = params.null_fraction ?? 0;
+  const null_bin_counts = new Array<number>(p_value_bin_counts.length).fill(0);
+
+  // Significant counts per sub-batch of whole antithetic pairs, for the variance reduction factor
+  const antithetic_batch_size = StatisticalUtils.varianceReductionBatchSize(num_simulations);
+  const antithetic_batch_counts = new Array<number>(20).fill(0);
+
+  // Optionally capture the first few uniforms (pre-transform) for RNG auditing
+  const iterationRandom = (index: number): RandomSource => {
+    const base = iterationStream(setup, index);
+    return rng_draws && rng_draws.length < rng_draw_limit
+      ? recordDraws(base, rng_draw_limit, rng_draws).random
+      : base;
+  };
+
+  // Running power at half-decade counts (10, 32, 100, 316, ...) plus the final count, so the
+  // last point is the reported power
+  const power_trace: Array<[number, number]> = [];
+  let trace_exponent = 2;
+  const nextTraceCount = () => Math.round(Math.pow(10, trace_exponent / 2));
+
+  // Progress is reported about every 1% of simulations, and always on the last one
+  const progress_step = Math.max(1, Math.ceil(num_simulations / 100));
+  const foldRecord = (record: IterationRecord, i: number): void => {
+    if (hooks.signal?.aborted) {
+      throw new SimulationError('cancelled', 'Simulation cancelled');
+    }
+    const { result, iteration_effect } = record;
+    if (result.noncentral_ci) noncentral_ci_switches++;
+    if (record.df !== undefined && record.df < LOW_DF_WARNING_THRESHOLD) low_df_count++;
+    if (record.capped_resamples !== undefined) {
+      capped_simulations++;
+      capped_resamples_sum += record.capped_resamples;
+    }
+    if (record.capped_permutations !== undefined) {
+      capped_permutation_simulations++;
+      capped_permutations_sum += record.capped_permutations;
+    }
+
+    if (record.samples) {
+      sample_datasets.push({ simulation_index: i, group1: record.samples[0], group2: record.samples[1] });
+    }
+
+    if (record.sample_stds) {
+      const [group1_std, group2_std] = record.sample_stds;
+      if (store_individual_results) {
+        group1_stds.push(group1_std);
+        group2_stds.push(group2_std);
+      } else {
+        if (Number.isFinite(group1_std)) group1_std_digest.add(group1_std);
+        if (Number.isFinite(group2_std)) group2_std_digest.add(group2_std);
+      }
+    }
+
+    if (record.bootstrap_mc_error) {
+      const [lower, upper] = record.bootstrap_mc_error;
+      bootstrap_mc_error_count++;
+      if (Number.isFinite(lower) && Number.isFinite(upper)) {
+        bootstrap_mc_finite_count++;
+        bootstrap_mc_lower_sum += lower;
+        bootstrap_mc_upper_sum += upper;
+      }
+    }
+
+    if (record.paired_p_values) {
+      const { t_test, mann_whitney } = record.paired_p_values;
+      const difference = t_test - mann_whitney;
+      if (store_individual_results) {
+        p_value_differences.push(difference);
+      } else if (Number.isFinite(difference)) {
+        p_value_difference_digest.add(difference);
+      }
+      p_value_difference_sum += difference;
+      if ((t_test < alpha_level) !== (mann_whitney < alpha_level)) {
+        test_disagreement_count++;
+      }
+    }
+
+    hooks.onResult?.(result, i);
+
+    if (scatter_sample_size > 0) {
+      const point: [number, number] = [result.effect_size, result.p_value];
+      if (i < scatter_sample_size) {
+        scatter_sample.push(point);
+      } else {
+        const slot = Math.floor(scatter_random() * (i + 1));
+        if (slot < scatter_sample_size) scatter_sample[slot] = point;
+      }
+    }
+
+    // mean_effect_size and effect_size_ci follow the chosen metric; the rest stay on effect_size
+    // (which already is Glass's delta under that metric)
+    const metric_value = effect_size_metric === 'hedges_g' ? result.hedges_g! : result.effect_size;
+    const p_value = result.p_value;
+    const ci_width = result.confidence_interval[1] - result.confidence_interval[0];
+    if (store_individual_results) {
+      // The percentile summary replaces the per-simulation rows in the output
+      if (!params.percentile_summary) results.push(result);
+      effect_sizes.push(metric_value);
+      cles_values.push(result.cles);
+      p_values.push(p_value);
+      ci_widths.push(ci_width);
+    } else {
+      effect_size_digest.add(metric_value);
+      cles_digest.add(result.cles);
+      if (!Number.isNaN(p_value)) p_value_digest.add(p_value);
+      if (Number.isFinite(ci_width)) ci_width_digest.add(ci_width);
+    }
+    const p_value_bin = StatisticalUtils.pValueBinIndex(p_value_edges, p_value);
+    if (p_value_bin >= 0) {
+      p_value_bin_counts[p_value_bin]++;
+      if (record.is_null) null_bin_counts[p_value_bin]++;
+    }
+    if (p_value > storey_lambda) p_value_count_above_lambda++;
+    if (p_value < alpha_level) unadjusted_significant_count++;
+    if (p_value < alpha_level / num_simulations) bonferroni_significant_count++;
+    const p_value_delta = p_value - p_value_running_mean;
+    p_value_running_mean += p_value_delta / (i + 1);
+    p_value_m2 += p_value_delta * (p_value - p_value_running_mean);
+    ci_width_sum += ci_width;
+    if (result.significant) significant_count++;
+    effect_size_sum += metric_value;
+    const delta = metric_value - effect_size_running_mean;
+    effect_size_running_mean += delta / (i + 1);
+    effect_size_m2 += delta * (metric_value - effect_size_running_mean);
+    cles_sum += result.cles;
+    if (pool_hedges_g && result.hedges_g !== undefined && Number.isFinite(result.hedges_g)) {
+      hedges_g_sum += result.hedges_g;
+      hedges_g_count++;
+    }
+    if (antithetic && result.significant && antithetic_batch_size > 0) {
+      const batch = Math.floor(i / antithetic_batch_size);
+      if (batch < antithetic_batch_counts.length) antithetic_batch_counts[batch]++;
+    }
+    if (classify_effect_sizes) {
+      effect_size_class_counts[StatisticalUtils.classifyEffectSize(result.effect_size, effect_size_thresholds)]++;
+    }
+    if (result.significant && iteration_effect !== 0) {
+      exaggeration_sum += Math.abs(result.effect_size) / Math.abs(iteration_effect);
+      if (Math.sign(result.effect_size) !== Math.sign(iteration_effect)) sign_error_count++;
+      design_analysis_count++;
+    }
+
+    // Last stratum is closed on the right so p = 1 is counted
+    const stratum = strata.find((st, k) => result.p_value >= st.p_low &&
+      (result.p_value < st.p_high || (k === strata.length - 1 && result.p_value <= st.p_high)));
+    if (stratum) {
+      stratum.effect_sum += result.effect_size;
+      stratum.abs_effect_sum += Math.abs(result.effect_size);
+      stratum.count++;
+    }
+
+    // Degenerate (NaN) intervals count toward neither coverage nor false coverage
+    const [ci_lower, ci_upper] = result.confidence_interval;
+    if (iteration_effect >= ci_lower && iteration_effect <= ci_upper) {
+      covered_count++;
+    } else if (iteration_effect < ci_lower || iteration_effect > ci_upper) {
+      not_covered_count++;
+    }
+
+    if (rope) {
+      if (ci_lower >= rope[0] && ci_upper <= rope[1]) {
+        rope_accept_count++;
+      } else if (ci_upper < rope[0] || ci_lower > rope[1]) {
+        rope_reject_count++;
+      }
+    }
+
+    const completed = i + 1;
+    if (completed % progress_step === 0 || completed === num_simulations) {
+      hooks.onProgress?.({ completed, total: num_simulations });
+    }
+
+    if (params.power_trace && (completed === nextTraceCount() || completed === num_simulations)) {
+      power_trace.push([completed, significant_count / completed]);
+      while (nextTraceCount() <= completed) trace_exponent++;
+    }
+  };
+
+  const worker_warning = await runIterations(setup, foldRecord, rng_draws ? iterationRandom : undefined);
+
+  const warnings: string[] = [];
+  if (worker_warning !== null) warnings.push(worker_warning);
+  if (low_df_count > 0) {
+    warnings.push(`${low_df_count} of ${num_simulations} simulations used fewer than ${LOW_DF_WARNING_THRESHOLD} ` +
+      'degrees of freedom; p-values are unstable there (consider larger samples or min_df)');
+  }
+
+  // Calculate aggregated statistics using jStat
+  // The population estimand reports the bias-corrected (Hedges' g) average instead of raw d;
+  // averaging g directly is already corrected, so the factor is not applied twice
+  const estimand = params.estimand ?? 'sample_distribution';
+  const raw_mean_effect_size = effect_size_sum / num_simulations;
+  const mean_effect_size = estimand === 'population_effect' && test_type !== 'mann_whitney' &&
+    effect_size_metric === 'cohens_d'
+    ? raw_mean_effect_size * StatisticalUtils.hedgesCorrection(test_type === 'paired' ? n1 - 1 : n1 + n2 - 2)
+    : raw_mean_effect_size;
+  const mean_ci_width = ci_width_sum / num_simulations;
+  const ci_coverage = covered_count / num_simulations;
+  // Counted directly rather than as 1 - coverage so it stays correct for other estimands
+  const false_coverage_rate = not_covered_count / num_simulations;
+
+  // Empirical 2.5/97.5 percentiles of the effect sizes and of the CLES values. CLES is taken
+  // from effect_size, not the chosen metric, so it gets its own percentiles on mean_cles' scale
+  const empiricalInterval = (values: number[], digest: TDigest): [number, number] => {
+    if (!store_individual_results) return [digest.quantile(0.025), digest.quantile(0.975)];
+    const sorted = [...values].sort((a, b) => a - b);
+    const lower_idx = Math.floor(0.025 * sorted.length);
+    const upper_idx = Math.floor(0.975 * sorted.length);
+    return [sorted[lower_idx], sorted[Math.min(upper_idx, sorted.length - 1)]];
+  };
+  const effect_size_ci = empiricalInterval(effect_sizes, effect_size_digest);
+  const cles_ci = empiricalInterval(cles_values, cles_digest);
+
+  const p_value_histogram = StatisticalUtils.pValueHistogramFromCounts(p_value_edges, p_value_bin_counts, alpha_level);
+  const pi0 = StatisticalUtils.storeyPi0(p_value_count_above_lambda, num_simulations, storey_lambda);
+  // Per-result significant flags stay on the raw p-values; only this count is corrected.
+  // Benjamini-Hochberg ranks the whole family, so it needs the stored p-values
+  const adjusted_significant_count =
+    multiple_comparison === 'none' ? unadjusted_significant_count
+    : multiple_comparison === 'bonferroni' ? bonferroni_significant_count
+    : store_individual_results ? StatisticalUtils.adjustedSignificantCount(p_values, alpha_level, multiple_comparison)
+    : null;
+  const ci_width_histogram = store_individual_results
+    ? StatisticalUtils.createHistogram(ci_widths)
+    : StatisticalUtils.createHistogramFromDigest(ci_width_digest);
+
+  const aggregated: AggregatedResults = {
     individual_results: results,
     significant_count,
     total_count: num_simulations,
     mean_effect_size,
     effect_size_ci,
+    // Sample (n - 1) standard deviations across simulations; NaN for a single simulation
+    effect_size_std: Math.sqrt(effect_size_m2 / (num_simulations - 1)),
+    p_value_std: Math.sqrt(p_value_m2 / (num_simulations - 1)),
+    mean_cles: cles_sum / num_simulations,
+    cles_ci,
     ci_coverage,
+    false_coverage_rate,
     mean_ci_width,
-    p_value_histogram
+    p_value_histogram,
+    ci_width_histogram,
+    effect_by_pvalue_stratum: strata.map(({ p_low, p_high, effect_sum, abs_effect_sum, count }) => ({
+      p_low,
+      p_high,
+      mean_effect: count > 0 ? effect_sum / count : null,
+      mean_abs_effect: count > 0 ? abs_effect_sum / count : null,
+      count
+    })),
+    power: significant_count / num_simulations,
+    power_ci: StatisticalUtils.powerExactCI(significant_count, num_simulations),
+    power_wilson_ci: StatisticalUtils.powerWilsonCI(significant_count, num_simulations),
+    type_m_error: design_analysis_count > 0 ? exaggeration_sum / design_analysis_count : null,
+    type_s_error: design_analysis_count > 0 ? sign_error_count / design_analysis_count : null,
+    // A sign is only wrong against a nonzero true effect, so without one this stays null too
+    wrong_direction_rate: design_analysis_count > 0 ? sign_error_count / significant_count : null,
+    pi0_estimate: pi0,
+    // A single bin leaves nothing to compare against uniform
+    uniformity_test: p_value_histogram.length >= 2 && num_simulations > 0
+      ? StatisticalUtils.chiSquareUniformity(p_value_histogram, num_simulations)
+      : null,
+    adjusted_significant_count,
+    // Precision as a fraction of the effect; undefined for (near-)zero mean effects
+    relative_ci_halfwidth: Math.abs(mean_effect_size) > 1e-6
+      ? mean_ci_width / (2 * Math.abs(mean_effect_size))
+      : null,
+    metadata: {
+      estimand,
+      effect_size_metric,
+      multiple_comparison,
+      ci_confidence_level,
+      warnings,
+      noncentral_ci_switches,
+      design_effect,
+      effective_sample_sizes: [n1 / design_effect, n2 / design_effect]
+    }
   };
+
+  if (classify_effect_sizes) {
+    aggregated.effect_size_class_counts = effect_size_class_counts;
+  }
+
+  if (dataset_count > 0) {
+    aggregated.sample_datasets = sample_datasets;
+  }
+
+  if (scatter_sample_size > 0) {
+    aggregated.scatter_sample = scatter_sample;
+  }
+
+  if (params.power_trace) {
+    aggregated.power_trace = power_trace;
+  }
+
+  const pooled_hedges_variance = hedges_g_count > 0
+    ? StatisticalUtils.hedgesGVariance(hedges_g_sum / hedges_g_count, n1, n2)
+    : NaN;
+  if (pooled_hedges_variance > 0) {
+    const estimate = hedges_g_sum / hedges_g_count;
+    const se = Math.sqrt(pooled_hedges_variance / hedges_g_count);
+    const z = (jStat as any).normal.inv(0.975, 0, 1);
+    aggregated.pooled_hedges_g = { estimate, se, ci: [estimate - z * se, estimate + z * se] };
+  }
+
+  if (params.percentile_summary) {
+    aggregated.percentile_summary = store_individual_results
+      ? {
+        p_value: StatisticalUtils.summarizeValues(p_values),
+        effect_size: StatisticalUtils.summarizeValues(effect_sizes),
+        s_value: StatisticalUtils.summarizeValues(p_values.map(p => StatisticalUtils.calculateSValue(p))),
+        ci_width: StatisticalUtils.summarizeValues(ci_widths)
+      }
+      : {
+        p_value: StatisticalUtils.summarizeQuantiles(q => p_value_digest.quantile(q)),
+        effect_size: StatisticalUtils.summarizeQuantiles(q => effect_size_digest.quantile(q)),
+        // -log2(p) is decreasing, so its q-th quantile sits at the p-value's (1 - q)-th
+        s_value: StatisticalUtils.summarizeQuantiles(q => StatisticalUtils.calculateSValue(p_value_digest.quantile(1 - q))),
+        ci_width: StatisticalUtils.summarizeQuantiles(q => ci_width_digest.quantile(q))
+      };
+  }
+
+  if (null_fraction > 0) {
+    // Same bins as p_value_histogram, counted separately for null and alternative simulations
+    aggregated.p_value_histogram_by_source = p_value_histogram.map((bin, k) => ({
+      bin_start: bin.bin_start,
+      bin_end: bin.bin_end,
+      null_count: null_bin_counts[k],
+      alt_count: bin.count - null_bin_counts[k]
+    }));
+  }
+
+  if (rope) {
+    // Degenerate (NaN) intervals fall through to undecided
+    const rope_decisions: RopeDecisions = {
+      rope,
+      accept_rate: rope_accept_count / num_simulations,
+      reject_rate: rope_reject_count / num_simulations,
+      undecided_rate: (num_simulations - rope_accept_count - rope_reject_count) / num_simulations
+    };
+    aggregated.rope_decisions = rope_decisions;
+  }
+
+  if (params.compare_tests) {
+    const test_agreement: TestAgreement = {
+      mean_p_value_difference: p_value_difference_sum / num_simulations,
+      p_value_difference_histogram: store_individual_results
+        ? StatisticalUtils.createHistogram(p_value_differences)
+        : StatisticalUtils.createHistogramFromDigest(p_value_difference_digest),
+      disagreement_rate: test_disagreement_count / num_simulations
+    };
+    aggregated.test_agreement = test_agreement;
+  }
+
+  if (effect_prior) {
+    // Rejection rate averaged over the prior on the true effect
+    aggregated.assurance = significant_count / num_simulations;
+  }
+
+  if (size_calibration) {
+    aggregated.metadata.size_calibration = size_calibration;
+  }
+
+  if (test_type === 'permutation') {
+    // Permutation p-values are discrete, so the nominal alpha may not be attainable exactly
+    aggregated.metadata.achievable_alpha = StatisticalUtils.nearestAchievableAlpha(
+      StatisticalUtils.achievableAlphaLevels(n1, n2, num_permutations, params.alternative), alpha_level);
+  }
+
+  if (params.per_simulation_timeout_ms !== undefined) {
+    aggregated.metadata.timeout_cap = {
+      per_simulation_timeout_ms: params.per_simulation_timeout_ms,
+      capped_simulations,
+      mean_capped_resamples: capped_simulations > 0 ? capped_resamples_sum / capped_simulations : null,
+      capped_permutation_simulations,
+      mean_capped_permutations: capped_permutation_simulations > 0
+        ? capped_permutations_sum / capped_permutation_simulations
+        : null
+    };
+  }
+
+  if (antithetic) {
+    aggregated.metadata.variance_reduction_factor =
+      StatisticalUtils.varianceReductionFromBatches(antithetic_batch_counts, antithetic_batch_size);
+  }
+
+  if (rng_draws) {
+    aggregated.metadata.rng_draws = rng_draws;
+  }
+
+  if (num_endpoints > 1) {
+    // Separate stream so the endpoint draws never overlap the per-simulation streams
+    aggregated.endpoint_analysis = await runEndpointAnalysis(params, createIndexedRandom(params.seed, 0, 1));
+  }
+
+  if (params.include_null_calibration) {
+    // Full-size companion run under the null: its rejection rate is the empirical Type I error
+    const null_run = await executeSimulation({
+      ...nullRunParams(params, num_simulations, 5),
+      store_individual_results: false
+    });
+    aggregated.null_rejection_rate = null_run.significant_count / null_run.total_count;
+  }
+
+  if (params.diagnostics) {
+    // Sampling distribution of the observed SDs around the population SDs
+    aggregated.group1_std_histogram = store_individual_results
+      ? StatisticalUtils.createHistogram(group1_stds)
+      : StatisticalUtils.createHistogramFromDigest(group1_std_digest);
+    aggregated.group2_std_histogram = store_individual_results
+      ? StatisticalUtils.createHistogram(group2_stds)
+      : StatisticalUtils.createHistogramFromDigest(group2_std_digest);
+  }
+
+  if (bootstrap_mc_error_count > 0) {
+    // Jackknife-after-bootstrap: errors near the CI widths mean n_boot is too small
+    aggregated.bootstrap_mc_error = {
+      mean_lower_mc_error: bootstrap_mc_finite_count > 0 ? bootstrap_mc_lower_sum / bootstrap_mc_finite_count : null,
+      mean_upper_mc_error: bootstrap_mc_finite_count > 0 ? bootstrap_mc_upper_sum / bootstrap_mc_finite_count : null
+    };
+  }
+
+  return aggregated;
 }
 
 export class MultiPairSimulationEngine {
@@ -239,13 +3418,14 @@ export class MultiPairSimulationEngine {
 
   async runMultiPairSimulation(
     params: MultiPairSimulationParams,
-    onProgress?: (progress: SimulationProgress) => void
+    onProgress?: (progress: SimulationProgress) => void,
+    signal?: AbortSignal
   ): Promise<MultiPairResults> {
     const startTime = performance.now();
     const enabledPairs = params.pairs.filter(p => p.enabled);
 
     if (enabledPairs.length === 0) {
-      throw new Error('No sample pairs enabled for simulation');
+      throw new SimulationError('invalid_parameter', 'No sample pairs enabled for simulation');
     }
 
     const results: PairResult[] = [];
@@ -268,6 +3448,7 @@ export class MultiPairSimulationEngine {
       const pairResult = await this.runSinglePairSimulation(
         pair,
         params.global_settings,
+        i,
         (simProgress) => {
           onProgress?.({
             currentPair: i + 1,
@@ -277,7 +3458,8 @@ export class MultiPairSimulationEngine {
             phase: 'running_simulations',
             pairName: pair.name
           });
-        }
+        },
+        signal
       );
 
       results.push(pairResult);
@@ -307,21 +3489,29 @@ export class MultiPairSimulationEngine {
   private async runSinglePairSimulation(
     pair: SamplePair,
     settings: GlobalSimulationSettings,
-    onProgress?: (progress: { completed: number; total: number }) => void
+    pairIndex: number,
+    onProgress?: (progress: { completed: number; total: number }) => void,
+    signal?: AbortSignal
   ): Promise<PairResult> {
-    // Convert pair to legacy format for existing simulation engine
-    const legacyParams = {
+    // Convert pair to legacy format for existing simulation engine; with a global seed each
+    // pair gets its own derived seed, so pairs stay independent yet the whole run reproduces
+    const legacyParams: SimulationParams = {
       group1_mean: pair.group1.mean,
       group1_std: pair.group1.std,
       group2_mean: pair.group2.mean,
       group2_std: pair.group2.std,
       sample_size_per_group: pair.sample_size_per_group,
+      group2_sample_size: pair.group2_sample_size,
+      group1_distribution: { type: pair.group1.distribution_type ?? 'normal' },
+      group2_distribution: { type: pair.group2.distribution_type ?? 'normal' },
       num_simulations: settings.num_simulations,
       hypothesized_effect_size: 0, // Will be calculated
-      alpha_level: 0.05 // Default, will be overridden by significance analysis
+      alpha_level: 0.05, // Default, will be overridden by significance analysis
+      test_type: settings.test_type,
+      seed: settings.random_seed === undefined ? undefined : deriveSeed(settings.random_seed, pairIndex, 3)
     };
 
-    const legacyResults = await runStatisticalSimulation(legacyParams);
+    const legacyResults = await runStatisticalSimulation(legacyParams, { onProgress, signal });
 
     // Convert legacy results to new format
     const individual_results = legacyResults.individual_results;
@@ -527,15 +3717,12 @@ export class MultiPairSimulationEngine {
     target_power: number,
     alpha: number
   ): number {
-    // Simplified calculation based on Cohen's formulas
-    // For two-sample t-test: n = (z_α/2 + z_β)² / d²
-    const z_alpha = 1.96; // For α = 0.05
-    const z_beta = 0.84;  // For 80% power
-
-    if (effect_size === 0) return Infinity;
-
-    const n = Math.pow(z_alpha + z_beta, 2) / Math.pow(effect_size, 2);
-    return Math.ceil(n);
+    // Unreachable targets (e.g. a zero effect) report Infinity rather than failing the analysis
+    try {
+      return requiredSampleSize(effect_size, alpha, target_power);
+    } catch {
+      return Infinity;
+    }
   }
 
   private analyzeSignificanceCorrelation(results: PairResult[]): SignificanceCorrelation {
@@ -0,0 +1,66 @@
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import { presetService } from './preset.service';
+import { SimulationParams } from '../types/simulation.types';
+
+// Minimal in-memory stand-in for the browser's localStorage
+const storage = new Map<string, string>();
+vi.stubGlobal('localStorage', {
+  getItem: (key: string) => storage.get(key) ?? null,
+  setItem: (key: string, value: string) => { storage.set(key, value); },
+  removeItem: (key: string) => { storage.delete(key); }
+});
+
+const params: SimulationParams = {
+  group1_mean: 0.5,
+  group1_std: 1,
+  group2_mean: 0,
+  group2_std: 1,
+  sample_size_per_group: 20,
+  num_simulations: 1000,
+  hypothesized_effect_size: 0.5,
+  alpha_level: 0.05,
+  seed: 226,
+  test_type: 'welch',
+  group2_sample_size: 30,
+  ci_method: { type: 'bootstrap_percentile', n_boot: 1000 },
+  effect_prior: { type: 'normal', mean: 0.5, sd: 0.2 },
+  group1_distribution: { type: 'student_t', df: 5 },
+  group1_contamination: { fraction: 0.1, scale: 3 },
+  group2_contamination: { fraction: 0.05, scale: 5 },
+  rope: [-0.1, 0.1]
+};
+
+describe('presetService', () => {
+  beforeEach(() => storage.clear());
+
+  it('saves, lists, loads and deletes presets by trimmed name', async () => {
+    await presetService.savePreset('  medium effect ', params);
+    await presetService.savePreset('another', { ...params, sample_size_per_group: 50 });
+
+    expect(await presetService.listPresets()).toEqual(['another', 'medium effect']);
+    expect(await presetService.loadPreset('medium effect')).toEqual(params);
+
+    await presetService.deletePreset('medium effect');
+    expect(await presetService.listPresets()).toEqual(['another']);
+    await expect(presetService.loadPreset('medium effect')).rejects.toThrow('not found');
+  });
+
+  it('treats prototype member names as ordinary preset names', async () => {
+    await expect(presetService.loadPreset('constructor')).rejects.toThrow('not found');
+    await expect(presetService.loadPreset('toString')).rejects.toThrow('not found');
+
+    await presetService.savePreset('__proto__', params);
+    expect(await presetService.listPresets()).toEqual(['__proto__']);
+    expect(await presetService.loadPreset('__proto__')).toEqual(params);
+  });
+
+  it('rejects empty names', async () => {
+    await expect(presetService.savePreset('   ', params)).rejects.toThrow();
+  });
+
+  it('starts over from a corrupt store', async () => {
+    vi.spyOn(console, 'warn').mockImplementation(() => {});
+    storage.set('statdash-simulation-presets', '{not json');
+    expect(await presetService.listPresets()).toEqual([]);
+  });
+});
@@ -0,0 +1,58 @@
+import { SimulationParams } from '../types/simulation.types';
+
+// Named simulation presets persisted as a single JSON document in localStorage
+const PRESET_STORAGE_KEY = 'statdash-simulation-presets';
+
+// A Map, so names such as '__proto__' or 'constructor' are plain keys rather than
+// prototype members
+type PresetStore = Map<string, SimulationParams>;
+
+class PresetService {
+  private readStore(): PresetStore {
+    const raw = localStorage.getItem(PRESET_STORAGE_KEY);
+    if (!raw) return new Map();
+
+    try {
+      const parsed = JSON.parse(raw);
+      if (parsed && typeof parsed === 'object' && !Array.isArray(parsed)) {
+        return new Map(Object.entries(parsed as Record<string, SimulationParams>));
+      }
+      console.warn('Preset store has an unexpected shape; starting from an empty store');
+    } catch (error) {
+      console.warn('Preset store is corrupt; starting from an empty store', error);
+    }
+    return new Map();
+  }
+
+  private writeStore(store: PresetStore): void {
+    localStorage.setItem(PRESET_STORAGE_KEY, JSON.stringify(Object.fromEntries(store)));
+  }
+
+  async savePreset(name: string, params: SimulationParams): Promise<void> {
+    const trimmed = name.trim();
+    if (!trimmed) throw new Error('Preset name must not be empty');
+
+    const store = this.readStore();
+    store.set(trimmed, params);
+    this.writeStore(store);
+  }
+
+  async loadPreset(name: string): Promise<SimulationParams> {
+    const preset = this.readStore().get(name.trim());
+    if (!preset) throw new Error(`Preset "${name}" not found`);
+    return preset;
+  }
+
+  async listPresets(): Promise<string[]> {
+    return Array.from(this.readStore().keys()).sort();
+  }
+
+  async deletePreset(name: string): Promise<void> {
+    const store = this.readStore();
+    store.delete(name.trim());
+    this.writeStore(store);
+  }
+}
+
+// Singleton instance
+export const presetService = new PresetService();
@@ -0,0 +1,16 @@
+import { bench, describe } from 'vitest';
+import { StatisticalUtils } from './multi-pair-simulation';
+import { createSeededRandom } from '../utils/random';
+
+// Per-draw Box-Muller against the buffered inverse-CDF path (buffered_sampling)
+describe.each([20, 200, 2000])('normal samples, n = %i', (n) => {
+  const random = createSeededRandom(217);
+
+  bench('per-draw Box-Muller', () => {
+    StatisticalUtils.generateSamples(n, 0, 1, undefined, random, false);
+  });
+
+  bench('buffered inverse CDF', () => {
+    StatisticalUtils.generateSamples(n, 0, 1, undefined, random, true);
+  });
+});
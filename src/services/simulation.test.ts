@@ -0,0 +1,1614 @@
+import { afterEach, describe, it, expect, vi } from 'vitest';
+import {
+  IterationSetup,
+  MultiPairSimulationEngine,
+  SimulationError,
+  analyzePaired,
+  calibrationCurve,
+  ciWidthCurve,
+  coverageVsDF,
+  powerAcrossAlphas,
+  requiredSampleSize,
+  StatisticalUtils,
+  runAllocationSweep,
+  runPowerGrid,
+  runSampleSizeSweep,
+  runSimulationBatch,
+  runStatisticalSimulation,
+  runStatisticalSimulationArrays,
+  runStatisticalSimulationStreamed,
+  simulateRange,
+  simulateReplications
+} from './multi-pair-simulation';
+import { AggregatedResults, MultiPairSimulationParams, MultipleComparison, SimulationEvent, SimulationParams } from '../types/simulation.types';
+import { createIndexedRandom } from '../utils/random';
+
+// Small seeded design; tests override what they exercise
+const baseParams = (overrides: Partial<SimulationParams> = {}): SimulationParams => ({
+  group1_mean: 0.5,
+  group1_std: 1,
+  group2_mean: 0,
+  group2_std: 1,
+  sample_size_per_group: 20,
+  num_simulations: 500,
+  hypothesized_effect_size: 0.5,
+  alpha_level: 0.05,
+  seed: 12345,
+  ...overrides
+});
+
+describe('mixture populations', () => {
+  const bimodal = {
+    type: 'mixture' as const,
+    components: [
+      { weight: 0.5, mean: -1.5, std: 0.5 },
+      { weight: 0.5, mean: 2.5, std: 0.5 }
+    ]
+  };
+
+  it('takes the true effect from the mixture moments', async () => {
+    // Mixture mean 0 + 0.5 and variance 4.5 - 0.5^2 = 4.25, against a normal(0, 1) group 2
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      group1_distribution: bimodal,
+      num_simulations: 1000
+    }));
+    const population_std = Math.sqrt((4.25 + 1) / 2);
+    // Coverage is judged against that truth, so it stays near nominal
+    expect(results.ci_coverage).toBeGreaterThan(0.9);
+    expect(Math.abs(results.mean_effect_size - 0.5 / population_std)).toBeLessThan(0.05);
+  });
+
+  it('produces null data in the companion null run', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      group1_distribution: bimodal,
+      num_simulations: 2000,
+      include_null_calibration: true
+    }));
+    expect(results.null_rejection_rate!).toBeGreaterThan(0.025);
+    expect(results.null_rejection_rate!).toBeLessThan(0.075);
+  });
+});
+
+describe('Laplace populations', () => {
+  it('keep the configured standardized effect', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      group1_distribution: { type: 'laplace' },
+      group2_distribution: { type: 'laplace' },
+      estimand: 'population_effect',
+      num_simulations: 2000
+    }));
+    // With scale tied to the group std the populations differ by d = 0.5
+    expect(Math.abs(results.mean_effect_size - 0.5)).toBeLessThan(0.03);
+    expect(results.ci_coverage).toBeGreaterThan(0.92);
+  });
+});
+
+describe('runStatisticalSimulationArrays', () => {
+  it('returns the same seeded series as the full run', async () => {
+    const params = baseParams({ self_calibrate: true, ci_method: { type: 'bootstrap_percentile', n_boot: 200 }, num_simulations: 200 });
+    const full = await runStatisticalSimulation(params);
+    const arrays = await runStatisticalSimulationArrays(params);
+
+    expect(Array.from(arrays.p_values)).toEqual(full.individual_results.map(r => r.p_value));
+    expect(Array.from(arrays.effect_sizes)).toEqual(full.individual_results.map(r => r.effect_size));
+  });
+});
+
+describe('per-simulation timeout', () => {
+  it('cuts permutation tests short and reports the permutations drawn', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      test_type: 'permutation',
+      num_permutations: 5000,
+      per_simulation_timeout_ms: 1e-6,
+      num_simulations: 50
+    }));
+    const cap = results.metadata.timeout_cap!;
+    expect(cap.capped_permutation_simulations).toBe(50);
+    expect(cap.mean_capped_permutations!).toBeLessThan(5000);
+    expect(cap.capped_simulations).toBe(0);
+  });
+
+  it('cuts bootstrap CIs short after the floor of resamples', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      ci_method: { type: 'bootstrap_t', n_boot: 2000 },
+      per_simulation_timeout_ms: 1e-6,
+      num_simulations: 50
+    }));
+    const cap = results.metadata.timeout_cap!;
+    // The floor of 20 is kept, then the first deadline check (every 16) stops at 32
+    expect(cap.capped_simulations).toBe(50);
+    expect(cap.mean_capped_resamples).toBe(32);
+    expect(cap.capped_permutation_simulations).toBe(0);
+    results.individual_results.forEach(r => expect(r.confidence_interval[0]).toBeLessThan(r.confidence_interval[1]));
+  });
+
+  it('leaves runs within the limit untouched', async () => {
+    const capped = await runStatisticalSimulation(baseParams({
+      ci_method: { type: 'bootstrap_t', n_boot: 100 },
+      per_simulation_timeout_ms: 60000,
+      num_simulations: 20
+    }));
+    const uncapped = await runStatisticalSimulation(baseParams({
+      ci_method: { type: 'bootstrap_t', n_boot: 100 },
+      num_simulations: 20
+    }));
+    expect(capped.metadata.timeout_cap!.capped_simulations).toBe(0);
+    expect(capped.individual_results).toEqual(uncapped.individual_results);
+    expect(uncapped.metadata.timeout_cap).toBeUndefined();
+  });
+});
+
+describe('runPowerGrid', () => {
+  it('rises along both axes and matches the same cell run on its own', async () => {
+    const base = baseParams({ num_simulations: 400, parallel_workers: 4 });
+    const cells = await runPowerGrid(base, [10, 40], [0.2, 0.8]);
+    const power = (n: number, effect: number) => cells.find(c => c.n === n && c.effect === effect)!.power;
+
+    expect(cells).toHaveLength(4);
+    expect(power(40, 0.2)).toBeGreaterThan(power(10, 0.2));
+    expect(power(40, 0.8)).toBeGreaterThan(power(10, 0.8));
+    expect(power(10, 0.8)).toBeGreaterThan(power(10, 0.2));
+    expect(power(40, 0.8)).toBeGreaterThan(power(40, 0.2));
+
+    const single = await runStatisticalSimulation({
+      ...base,
+      sample_size_per_group: 40,
+      effect_prior: { type: 'point', value: 0.8 },
+      store_individual_results: false,
+      parallel_workers: 1
+    });
+    expect(power(40, 0.8)).toBe(single.power);
+  });
+});
+
+describe('store_individual_results: false', () => {
+  it('drops the rows but keeps the exact aggregates', async () => {
+    const stored = await runStatisticalSimulation(baseParams());
+    const streamed = await runStatisticalSimulation(baseParams({ store_individual_results: false }));
+
+    expect(streamed.individual_results).toHaveLength(0);
+    expect(streamed.power).toBe(stored.power);
+    expect(streamed.mean_ci_width).toBe(stored.mean_ci_width);
+    expect(streamed.p_value_std).toBeCloseTo(stored.p_value_std, 12);
+    expect(streamed.pi0_estimate).toBe(stored.pi0_estimate);
+    expect(streamed.adjusted_significant_count).toBe(stored.adjusted_significant_count);
+    expect(streamed.p_value_histogram).toEqual(stored.p_value_histogram);
+    // Effect-size percentiles come from the t-digest instead
+    expect(Math.abs(streamed.effect_size_ci[0] - stored.effect_size_ci[0])).toBeLessThan(0.1);
+    expect(Math.abs(streamed.effect_size_ci[1] - stored.effect_size_ci[1])).toBeLessThan(0.1);
+  });
+
+  it('bins data-ranged histograms over the same edges from the digest', async () => {
+    const options = { diagnostics: true, compare_tests: true, percentile_summary: true };
+    const stored = await runStatisticalSimulation(baseParams(options));
+    const streamed = await runStatisticalSimulation(baseParams({ ...options, store_individual_results: false }));
+
+    for (const key of ['ci_width_histogram', 'group1_std_histogram', 'group2_std_histogram'] as const) {
+      const exact = stored[key]!;
+      const approx = streamed[key]!;
+      expect(approx.map(bin => [bin.bin_start, bin.bin_end])).toEqual(exact.map(bin => [bin.bin_start, bin.bin_end]));
+      expect(approx.reduce((sum, bin) => sum + bin.count, 0)).toBe(500);
+      approx.forEach((bin, k) => expect(Math.abs(bin.count - exact[k].count)).toBeLessThanOrEqual(10));
+    }
+    expect(streamed.test_agreement!.mean_p_value_difference)
+      .toBe(stored.test_agreement!.mean_p_value_difference);
+
+    const stored_summary = stored.percentile_summary!;
+    const streamed_summary = streamed.percentile_summary!;
+    expect(streamed_summary.p_value.min).toBe(stored_summary.p_value.min);
+    expect(streamed_summary.ci_width.max).toBe(stored_summary.ci_width.max);
+    expect(Math.abs(streamed_summary.p_value.p50 - stored_summary.p_value.p50)).toBeLessThan(0.02);
+    expect(streamed_summary.s_value.max).toBeCloseTo(stored_summary.s_value.max, 10);
+    expect(Math.abs(streamed_summary.s_value.p50 - stored_summary.s_value.p50)).toBeLessThan(0.1);
+  });
+
+  it('counts Bonferroni but leaves Benjamini-Hochberg unavailable', async () => {
+    const bonferroni = { multiple_comparison: 'bonferroni' as const };
+    expect((await runStatisticalSimulation(baseParams({ ...bonferroni, store_individual_results: false })))
+      .adjusted_significant_count)
+      .toBe((await runStatisticalSimulation(baseParams(bonferroni))).adjusted_significant_count);
+
+    const bh = await runStatisticalSimulation(baseParams({
+      multiple_comparison: 'benjamini_hochberg',
+      store_individual_results: false
+    }));
+    expect(bh.adjusted_significant_count).toBeNull();
+  });
+});
+
+describe('p-value uniformity test', () => {
+  it('does not reject under the null', async () => {
+    const results = await runStatisticalSimulation(baseParams({ group1_mean: 0, num_simulations: 2000 }));
+    expect(results.uniformity_test!.df).toBe(19);
+    expect(results.uniformity_test!.p_value).toBeGreaterThan(0.001);
+  });
+
+  it('is skipped rather than thrown with a single histogram bin', async () => {
+    const params = baseParams({ histogram_bins: 1 });
+    const results = await runStatisticalSimulation(params);
+    expect(results.uniformity_test).toBeNull();
+
+    const arrays = await runStatisticalSimulationArrays(params);
+    expect(arrays.p_values).toHaveLength(500);
+  });
+});
+
+describe('SimulationError kinds', () => {
+  const kindOf = async (run: () => unknown): Promise<string | undefined> => {
+    try {
+      await run();
+    } catch (error) {
+      expect(error).toBeInstanceOf(SimulationError);
+      return (error as SimulationError).kind;
+    }
+    return undefined;
+  };
+
+  it('reports what went wrong', async () => {
+    expect(await kindOf(() => runStatisticalSimulation(baseParams({ alpha_level: 1.5 })))).toBe('invalid_alpha');
+    expect(await kindOf(() => runStatisticalSimulation(baseParams({ group2_std: 0 })))).toBe('invalid_std');
+    expect(await kindOf(() => runStatisticalSimulation(baseParams({ sample_size_per_group: 1 })))).toBe('empty_group');
+    expect(await kindOf(() => runStatisticalSimulation(baseParams({ parallel_workers: 0 })))).toBe('invalid_parameter');
+    expect(await kindOf(() => StatisticalUtils.pairedTTest([1, 2], [1, 2, 3]))).toBe('invalid_parameter');
+    expect(await kindOf(() => StatisticalUtils.mannWhitneyU([], [1, 2]))).toBe('empty_group');
+  });
+
+  it('covers the helpers outside the main run', async () => {
+    expect(await kindOf(() => StatisticalUtils.pooledStd(0, 10, 1, 10))).toBe('invalid_std');
+    expect(await kindOf(() => StatisticalUtils.compareTests([1, 2, 3], [2, 3, 4], 1))).toBe('invalid_alpha');
+    expect(await kindOf(() => StatisticalUtils.effectSizeCIWidth(0.5, 1, 20))).toBe('empty_group');
+    expect(await kindOf(() => requiredSampleSize(0.5, 0.05, 1.5))).toBe('invalid_parameter');
+    expect(await kindOf(() => runSimulationBatch([]))).toBe('invalid_parameter');
+    expect(await kindOf(() => runPowerGrid(baseParams(), [], [0.5]))).toBe('invalid_parameter');
+    expect(await kindOf(() => new MultiPairSimulationEngine().runMultiPairSimulation({
+      pairs: [],
+      global_settings: { num_simulations: 10, significance_levels: [0.05], confidence_level: 0.95, test_type: 'pooled' },
+      ui_preferences: { theme: 'light', decimal_places: 3, chart_animations: false, color_blind_safe: false }
+    }))).toBe('invalid_parameter');
+  });
+
+  it('reports a cancelled run', async () => {
+    const abort = new AbortController();
+    const run = runStatisticalSimulation(baseParams({ num_simulations: 1000 }), {
+      signal: abort.signal,
+      onProgress: ({ completed }) => { if (completed >= 100) abort.abort(); }
+    });
+    expect(await kindOf(() => run)).toBe('cancelled');
+  });
+});
+
+describe('verify_reproducibility', () => {
+  it('passes for a seeded run and returns the same results as running it once', async () => {
+    const once = await runStatisticalSimulation(baseParams({ num_simulations: 200 }));
+    const verified = await runStatisticalSimulation(baseParams({ num_simulations: 200, verify_reproducibility: true }));
+    expect(verified).toEqual(once);
+  });
+
+  it('gives different draws for different seeds', async () => {
+    const a = await runStatisticalSimulation(baseParams({ num_simulations: 50 }));
+    const b = await runStatisticalSimulation(baseParams({ num_simulations: 50, seed: 54321 }));
+    expect(a.individual_results[0].p_value).not.toBe(b.individual_results[0].p_value);
+  });
+});
+
+describe('diagnostics', () => {
+  it('histograms the realized sample SDs around each population SD', async () => {
+    const results = await runStatisticalSimulation(baseParams({ group1_std: 2, diagnostics: true }));
+    const center = (bins: { bin_start: number; bin_end: number; count: number }[]) =>
+      bins.reduce((sum, b) => sum + b.count * (b.bin_start + b.bin_end) / 2, 0) / bins.reduce((sum, b) => sum + b.count, 0);
+
+    for (const [bins, sd] of [[results.group1_std_histogram!, 2], [results.group2_std_histogram!, 1]] as const) {
+      expect(bins.reduce((sum, b) => sum + b.count, 0)).toBe(500);
+      // E[s] = c4 sigma, about 0.987 sigma at n = 20
+      expect(Math.abs(center(bins) - 0.987 * sd)).toBeLessThan(0.05 * sd);
+    }
+  });
+
+  it('is off by default', async () => {
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 20 }));
+    expect(results.group1_std_histogram).toBeUndefined();
+  });
+});
+
+describe('significance rules', () => {
+  it('agree between the p-value and the pooled analytic CI, and the SESOI only removes rejections', async () => {
+    const by_p = await runStatisticalSimulation(baseParams());
+    const by_ci = await runStatisticalSimulation(baseParams({ significance_rule: { type: 'ci_excludes_null' } }));
+    const with_sesoi = await runStatisticalSimulation(baseParams({ significance_rule: { type: 'p_value_and_sesoi', sesoi: 0.8 } }));
+
+    // The d interval excludes 0 exactly when |t| exceeds the critical value
+    expect(by_ci.significant_count).toBe(by_p.significant_count);
+    expect(with_sesoi.significant_count).toBeLessThan(by_p.significant_count);
+    with_sesoi.individual_results.forEach((r, i) => {
+      expect(r.significant).toBe(by_p.individual_results[i].significant && Math.abs(r.effect_size) > 0.8);
+    });
+  });
+});
+
+describe('estimand', () => {
+  it("reports the Hedges-corrected mean under population_effect", async () => {
+    const params = baseParams({ sample_size_per_group: 10, num_simulations: 4000 });
+    const sample = await runStatisticalSimulation(params);
+    const population = await runStatisticalSimulation({ ...params, estimand: 'population_effect' });
+
+    expect(sample.metadata.estimand).toBe('sample_distribution');
+    expect(population.metadata.estimand).toBe('population_effect');
+    expect(population.mean_effect_size).toBeCloseTo(sample.mean_effect_size * StatisticalUtils.hedgesCorrection(18), 12);
+    // Raw d overstates the true 0.5 by about 1 / J = 1.04 at df = 18; the corrected mean does not
+    expect(sample.mean_effect_size).toBeGreaterThan(0.5);
+    expect(Math.abs(population.mean_effect_size - 0.5)).toBeLessThan(0.025);
+  });
+});
+
+describe('bootstrap_t CI method', () => {
+  it('covers the true effect at about the nominal rate', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      ci_method: { type: 'bootstrap_t', n_boot: 400 },
+      num_simulations: 300
+    }));
+    // Binomial SD of the coverage estimate is about 0.013
+    expect(results.ci_coverage).toBeGreaterThan(0.9);
+    expect(results.ci_coverage).toBeLessThan(0.99);
+  });
+});
+
+describe('multiple endpoints', () => {
+  it('holds the family-wise error rate near alpha with Bonferroni under the null', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      num_endpoints: 3,
+      endpoint_correlation: 0.5,
+      num_simulations: 2000
+    }));
+    const analysis = results.endpoint_analysis!;
+    expect(analysis.adjusted_alpha).toBeCloseTo(0.05 / 3, 12);
+    expect(analysis.per_endpoint_power).toHaveLength(3);
+    // At most 1 - (1 - 0.05 / 3)^3 = 0.049, less with positive correlation
+    expect(analysis.family_wise_error_rate).toBeLessThan(0.065);
+  });
+});
+
+describe('simulateReplications', () => {
+  it('replicates at the power of the observed design when the effect is taken as known', async () => {
+    // Power of a two-sided t-test at d = 0.5 with 64 per group is 0.80
+    const results = await simulateReplications(0.5, 0, 64, 64, 2000, 0.05, 218);
+    expect(Math.abs(results.replication_power - 0.8)).toBeLessThan(0.04);
+    expect(Math.abs(results.mean_effect_size - 0.5)).toBeLessThan(0.02);
+    expect(results.effect_sizes).toHaveLength(2000);
+  });
+
+  it('widens the spread of replication effects when the original uncertainty is propagated', async () => {
+    const known = await simulateReplications(0.5, 0, 64, 64, 1000, 0.05, 2180);
+    const uncertain = await simulateReplications(0.5, 0.3, 64, 64, 1000, 0.05, 2180);
+    const width = (r: { effect_size_interval: [number, number] }) => r.effect_size_interval[1] - r.effect_size_interval[0];
+    expect(width(uncertain)).toBeGreaterThan(width(known) * 1.3);
+  });
+});
+
+describe('relative_ci_halfwidth', () => {
+  it('is the mean CI half-width over the mean effect', async () => {
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 200 }));
+    expect(results.relative_ci_halfwidth).toBeCloseTo(results.mean_ci_width / (2 * Math.abs(results.mean_effect_size)), 12);
+  });
+
+  it('is undefined for a zero mean effect', async () => {
+    // Antithetic pairs mirror each other, so the mean d cancels up to rounding
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      antithetic_sampling: true,
+      alternative: 'greater',
+      num_simulations: 200
+    }));
+    expect(results.relative_ci_halfwidth).toBeNull();
+  });
+});
+
+describe('runAllocationSweep', () => {
+  it('peaks at the balanced split for equal SDs and skips splits leaving a group under 2', async () => {
+    const points = await runAllocationSweep(baseParams({ num_simulations: 1000 }), 60, [0.02, 0.2, 0.5, 0.8]);
+
+    expect(points.map(p => [p.n1, p.n2])).toEqual([[12, 48], [30, 30], [48, 12]]);
+    const [low, balanced, high] = points.map(p => p.power);
+    expect(balanced).toBeGreaterThan(low);
+    expect(balanced).toBeGreaterThan(high);
+  });
+});
+
+describe('coverageVsDF', () => {
+  it('orders Type I error by the df the statistic is referred to, with Welch near nominal', async () => {
+    const calibration = await coverageVsDF(baseParams({
+      group1_std: 3,
+      sample_size_per_group: 5,
+      group2_sample_size: 20,
+      num_simulations: 4000
+    }), [2, 1000]);
+    const [too_few, too_many] = calibration.forced_df.map(f => f.type1_error_rate);
+
+    // Each sample's Satterthwaite df lies between the two, so the rates are ordered
+    expect(too_few).toBeLessThanOrEqual(calibration.welch_type1_error_rate);
+    expect(calibration.welch_type1_error_rate).toBeLessThanOrEqual(too_many);
+    expect(too_many).toBeGreaterThan(0.065);
+    expect(Math.abs(calibration.welch_type1_error_rate - 0.05)).toBeLessThan(0.015);
+  });
+});
+
+describe('debug_rng', () => {
+  it('records the first uniforms of simulation 0 and leaves the results unchanged', async () => {
+    const plain = await runStatisticalSimulation(baseParams({ num_simulations: 50 }));
+    const audited = await runStatisticalSimulation(baseParams({ num_simulations: 50, debug_rng: true, debug_rng_draws: 8 }));
+
+    const stream = createIndexedRandom(12345, 0);
+    expect(audited.metadata.rng_draws).toEqual(Array.from({length: 8}, () => stream()));
+    expect(audited.individual_results).toEqual(plain.individual_results);
+  });
+});
+
+describe('percentile_summary', () => {
+  it('replaces the per-simulation rows with quantile summaries of the same values', async () => {
+    const full = await runStatisticalSimulation(baseParams());
+    const summary = await runStatisticalSimulation(baseParams({ percentile_summary: true }));
+
+    expect(summary.individual_results).toHaveLength(0);
+    const p_values = full.individual_results.map(r => r.p_value);
+    expect(summary.percentile_summary!.p_value).toEqual(StatisticalUtils.summarizeValues(p_values));
+    expect(summary.percentile_summary!.effect_size.p50)
+      .toBeCloseTo(StatisticalUtils.summarizeValues(full.individual_results.map(r => r.effect_size)).p50, 12);
+  });
+});
+
+describe('return_sample_datasets', () => {
+  it('returns the raw groups behind evenly spaced simulations', async () => {
+    const results = await runStatisticalSimulation(baseParams({ return_sample_datasets: 4 }));
+    const datasets = results.sample_datasets!;
+
+    expect(datasets.map(d => d.simulation_index)).toEqual([0, 125, 250, 375]);
+    for (const dataset of datasets) {
+      expect(dataset.group1).toHaveLength(20);
+      // The stored data reproduce that simulation's test
+      const { p_value } = StatisticalUtils.twoSampleTTest(dataset.group1, dataset.group2);
+      expect(p_value).toBeCloseTo(results.individual_results[dataset.simulation_index].p_value, 12);
+    }
+  });
+});
+
+describe('ci_width_histogram', () => {
+  it('sits at the fixed width of the analytic interval, which does not depend on d', async () => {
+    const results = await runStatisticalSimulation(baseParams());
+    const width = StatisticalUtils.effectSizeCIWidth(0.5, 20, 20);
+    // Only rounding separates the widths, so every bin lies at the analytic width
+    expect(results.ci_width_histogram.reduce((sum, b) => sum + b.count, 0)).toBe(500);
+    results.ci_width_histogram.forEach(b => expect(b.bin_start).toBeCloseTo(width, 10));
+    expect(results.mean_ci_width).toBeCloseTo(width, 10);
+  });
+
+  it('spreads the varying widths of the noncentral t interval', async () => {
+    const results = await runStatisticalSimulation(baseParams({ ci_method: { type: 'noncentral_t' }, num_simulations: 200 }));
+    expect(results.ci_width_histogram).toHaveLength(20);
+    expect(results.ci_width_histogram.reduce((sum, b) => sum + b.count, 0)).toBe(200);
+  });
+});
+
+describe('false_coverage_rate', () => {
+  it('is the complement of coverage when every interval is finite', async () => {
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 1000 }));
+    expect(results.ci_coverage + results.false_coverage_rate).toBeCloseTo(1, 12);
+    // About 1 - 0.95, within four standard errors
+    expect(results.false_coverage_rate).toBeGreaterThan(0.05 - 4 * Math.sqrt(0.05 * 0.95 / 1000) - 0.01);
+    expect(results.false_coverage_rate).toBeLessThan(0.05 + 4 * Math.sqrt(0.05 * 0.95 / 1000) + 0.01);
+  });
+});
+
+describe('noncentral_ci_threshold', () => {
+  it('switches only the effects beyond the threshold to the noncentral t interval', async () => {
+    const results = await runStatisticalSimulation(baseParams({ noncentral_ci_threshold: 0.8 }));
+    const switched = results.individual_results.filter(r => r.noncentral_ci);
+    expect(results.metadata.noncentral_ci_switches).toBe(switched.length);
+    expect(switched.length).toBeGreaterThan(0);
+    expect(switched.length).toBeLessThan(500);
+    switched.forEach(r => {
+      expect(Math.abs(r.effect_size)).toBeGreaterThan(0.8);
+      expect(r.confidence_interval).toEqual(StatisticalUtils.noncentralTCI(r.effect_size, 20, 20));
+    });
+  });
+
+  it('switches every interval at a threshold of zero', async () => {
+    const results = await runStatisticalSimulation(baseParams({ noncentral_ci_threshold: 0, num_simulations: 50 }));
+    expect(results.metadata.noncentral_ci_switches).toBe(50);
+  });
+});
+
+describe('runStatisticalSimulationStreamed', () => {
+  it('batches every result in order and ends with the aggregates', async () => {
+    const events: SimulationEvent[] = [];
+    const aggregated = await runStatisticalSimulationStreamed(baseParams({ num_simulations: 250 }), e => events.push(e), 100);
+
+    const batches = events.flatMap(e => (e.type === 'results' ? [e.results] : []));
+    expect(batches.map(b => b.length)).toEqual([100, 100, 50]);
+    expect(batches.flat()).toEqual(aggregated.individual_results);
+    expect(events.some(e => e.type === 'progress')).toBe(true);
+    expect(events[events.length - 1]).toEqual({ type: 'done', aggregated });
+  });
+
+  it('rejects a non-positive batch size', async () => {
+    await expect(runStatisticalSimulationStreamed(baseParams(), () => {}, 0)).rejects.toThrow('Batch size');
+  });
+});
+
+describe('cluster sampling', () => {
+  it('reports the design effect and effective sample sizes', async () => {
+    const results = await runStatisticalSimulation(baseParams({ cluster_size: 5, icc: 0.2, num_simulations: 50 }));
+    expect(results.metadata.design_effect).toBeCloseTo(1.8, 12);
+    expect(results.metadata.effective_sample_sizes[0]).toBeCloseTo(20 / 1.8, 10);
+    expect(results.metadata.effective_sample_sizes[1]).toBeCloseTo(20 / 1.8, 10);
+  });
+
+  it('inflates the null rejection rate of the naive t-test', async () => {
+    // Design effect 2.8 shrinks the effective critical value to about 1.96 / sqrt(2.8), so ~24% reject
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      cluster_size: 10,
+      icc: 0.2,
+      num_simulations: 1000
+    }));
+    expect(results.significant_count / 1000).toBeGreaterThan(0.12);
+  });
+
+  it('rejects an icc outside [0, 1)', async () => {
+    await expect(runStatisticalSimulation(baseParams({ cluster_size: 5, icc: 1 }))).rejects.toThrow('Intraclass');
+  });
+});
+
+describe('assurance', () => {
+  it('reduces to the fixed-effect power under a point prior', async () => {
+    const fixed = await runStatisticalSimulation(baseParams());
+    const point = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      effect_prior: { type: 'point', value: 0.5 }
+    }));
+    expect(fixed.assurance).toBeUndefined();
+    expect(point.assurance).toBe(fixed.significant_count / 500);
+  });
+
+  it('averages power over a normal prior on d', async () => {
+    // Normal approximation: d sqrt(n/2) ~ N(1.58, 1.58) and T ~ N(that, 1), so
+    // P(|T| > 2.02) = 0.433 (1.96 instead of the t critical value gives 0.449)
+    const results = await runStatisticalSimulation(baseParams({
+      effect_prior: { type: 'normal', mean: 0.5, sd: 0.5 },
+      num_simulations: 2000
+    }));
+    expect(Math.abs(results.assurance! - 0.44)).toBeLessThan(0.06);
+  });
+});
+
+describe('effect_by_pvalue_stratum', () => {
+  it('averages the effects of the simulations in each stratum', async () => {
+    const results = await runStatisticalSimulation(baseParams());
+    const strata = results.effect_by_pvalue_stratum;
+    expect(strata.map(s => [s.p_low, s.p_high])).toEqual([[0, 0.01], [0.01, 0.05], [0.05, 1]]);
+    expect(strata.reduce((sum, s) => sum + s.count, 0)).toBe(500);
+
+    strata.forEach((s, k) => {
+      const members = results.individual_results.filter(r =>
+        r.p_value >= s.p_low && (r.p_value < s.p_high || (k === strata.length - 1 && r.p_value <= s.p_high)));
+      expect(s.count).toBe(members.length);
+      expect(s.mean_effect!).toBeCloseTo(members.reduce((sum, r) => sum + r.effect_size, 0) / members.length, 10);
+    });
+  });
+
+  it('shows the winner\'s curse: the smallest p-values carry the largest effects', async () => {
+    const [strong, , weak] = (await runStatisticalSimulation(baseParams())).effect_by_pvalue_stratum;
+    expect(strong.mean_effect!).toBeGreaterThan(0.5);
+    expect(strong.mean_effect!).toBeGreaterThan(weak.mean_effect!);
+  });
+
+  it('rejects edges that do not increase', async () => {
+    await expect(runStatisticalSimulation(baseParams({ pvalue_strata: [0, 0.05, 0.05, 1] }))).rejects.toThrow('strata');
+  });
+});
+
+describe('rope_decisions', () => {
+  it('classifies each CI against the ROPE', async () => {
+    const rope: [number, number] = [-0.2, 0.2];
+    const results = await runStatisticalSimulation(baseParams({ rope }));
+    const inside = results.individual_results
+      .filter(r => r.confidence_interval[0] >= -0.2 && r.confidence_interval[1] <= 0.2).length;
+    const outside = results.individual_results
+      .filter(r => r.confidence_interval[1] < -0.2 || r.confidence_interval[0] > 0.2).length;
+
+    expect(results.rope_decisions!.rope).toEqual(rope);
+    expect(results.rope_decisions!.accept_rate).toBe(inside / 500);
+    expect(results.rope_decisions!.reject_rate).toBe(outside / 500);
+    expect(results.rope_decisions!.accept_rate + results.rope_decisions!.reject_rate +
+      results.rope_decisions!.undecided_rate).toBeCloseTo(1, 12);
+  });
+
+  it('never accepts when the ROPE is narrower than every CI', async () => {
+    // Analytic CIs at n = 20 per group are 2 * 2.024 * sqrt(0.1) = 1.28 wide
+    const results = await runStatisticalSimulation(baseParams({ rope: [-0.5, 0.5] }));
+    expect(results.rope_decisions!.accept_rate).toBe(0);
+    expect(results.rope_decisions!.reject_rate).toBeGreaterThan(0);
+  });
+
+  it('is absent without a ROPE and rejects a reversed one', async () => {
+    expect((await runStatisticalSimulation(baseParams({ num_simulations: 10 }))).rope_decisions).toBeUndefined();
+    await expect(runStatisticalSimulation(baseParams({ rope: [0.2, -0.2] }))).rejects.toThrow();
+  });
+});
+
+describe('compare_tests', () => {
+  it('summarizes t-test vs Mann-Whitney on the same datasets without changing the run', async () => {
+    const plain = await runStatisticalSimulation(baseParams());
+    const compared = await runStatisticalSimulation(baseParams({ compare_tests: true }));
+    expect(compared.individual_results).toEqual(plain.individual_results);
+
+    const agreement = compared.test_agreement!;
+    expect(agreement.p_value_difference_histogram.reduce((sum, b) => sum + b.count, 0)).toBe(500);
+    // Near-equivalent tests on normal data: they split on a minority of datasets
+    expect(agreement.disagreement_rate).toBeGreaterThan(0);
+    expect(agreement.disagreement_rate).toBeLessThan(0.2);
+    expect(Math.abs(agreement.mean_p_value_difference)).toBeLessThan(0.05);
+  });
+});
+
+describe('calibrationCurve', () => {
+  it('tracks the diagonal for the t-test on normal data, even when the params carry an effect', async () => {
+    const curve = await calibrationCurve(baseParams({ num_simulations: 2000 }), [0.01, 0.05, 0.1, 0.5]);
+    expect(curve.map(point => point.nominal_alpha)).toEqual([0.01, 0.05, 0.1, 0.5]);
+    curve.forEach(({ nominal_alpha, empirical_alpha }) => {
+      expect(Math.abs(empirical_alpha - nominal_alpha))
+        .toBeLessThan(4 * Math.sqrt(nominal_alpha * (1 - nominal_alpha) / 2000));
+    });
+    curve.slice(1).forEach((point, k) => expect(point.empirical_alpha).toBeGreaterThanOrEqual(curve[k].empirical_alpha));
+  });
+
+  it('rejects alphas outside (0, 1)', async () => {
+    await expect(calibrationCurve(baseParams(), [0.05, 1])).rejects.toThrow('Nominal alphas');
+  });
+});
+
+describe('antithetic_sampling', () => {
+  it('pairs each simulation with its mirror image', async () => {
+    // Under the null the mirrored draws negate the mean difference and keep the pooled SD,
+    // so the one-sided p-values of a pair sum to 1
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      antithetic_sampling: true,
+      alternative: 'greater',
+      num_simulations: 100
+    }));
+    for (let i = 0; i < 100; i += 2) {
+      const [first, mirror] = [results.individual_results[i], results.individual_results[i + 1]];
+      expect(mirror.effect_size).toBeCloseTo(-first.effect_size, 8);
+      expect(mirror.p_value).toBeCloseTo(1 - first.p_value, 8);
+    }
+  });
+
+  it('reduces the variance of the power estimate', async () => {
+    // Both members of a pair rejecting needs t > t_crit and -t > t_crit, so pairs are negatively
+    // correlated and the sub-batch spread falls below the binomial one
+    const null_run = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      antithetic_sampling: true,
+      alternative: 'greater',
+      num_simulations: 2000
+    }));
+    expect(null_run.metadata.variance_reduction_factor!).toBeGreaterThan(1);
+
+    const effect_run = await runStatisticalSimulation(baseParams({
+      antithetic_sampling: true,
+      alternative: 'greater',
+      num_simulations: 2000
+    }));
+    expect(effect_run.metadata.variance_reduction_factor!).toBeGreaterThan(1);
+  });
+
+  it('rejects two-sided tests', async () => {
+    await expect(runStatisticalSimulation(baseParams({ antithetic_sampling: true })))
+      .rejects.toThrow('one-sided alternative');
+  });
+
+  it('rejects mixture populations', async () => {
+    await expect(runStatisticalSimulation(baseParams({
+      antithetic_sampling: true,
+      alternative: 'greater',
+      group1_distribution: { type: 'mixture', components: [{ weight: 1, mean: 0, std: 1 }] }
+    }))).rejects.toThrow('Antithetic');
+  });
+
+  it('rejects draws the mirroring does not cover', async () => {
+    const antithetic = { antithetic_sampling: true, alternative: 'greater' as const };
+    await expect(runStatisticalSimulation(baseParams({ ...antithetic, null_fraction: 0.5 })))
+      .rejects.toThrow('null_fraction');
+    await expect(runStatisticalSimulation(baseParams({
+      ...antithetic,
+      effect_prior: { type: 'normal', mean: 0.5, sd: 0.2 }
+    }))).rejects.toThrow('effect prior');
+  });
+});
+
+describe('pi0_estimate', () => {
+  it('is near 1 under the null and lower when most simulations carry an effect', async () => {
+    const null_run = await runStatisticalSimulation(baseParams({ group1_mean: 0, num_simulations: 1000 }));
+    const effect_run = await runStatisticalSimulation(baseParams({ group1_mean: 1, num_simulations: 1000 }));
+    expect(null_run.pi0_estimate).toBeGreaterThan(0.85);
+    expect(effect_run.pi0_estimate).toBeLessThan(0.3);
+  });
+});
+
+describe('self_calibrate', () => {
+  it('estimates the size of a valid test near alpha without moving significance', async () => {
+    const plain = await runStatisticalSimulation(baseParams({ num_simulations: 2000 }));
+    const calibrated = await runStatisticalSimulation(baseParams({ num_simulations: 2000, self_calibrate: true }));
+    const calibration = calibrated.metadata.size_calibration!;
+
+    expect(calibration.calibration_simulations).toBe(2000);
+    expect(Math.abs(calibration.estimated_size - 0.05)).toBeLessThan(4 * Math.sqrt(0.05 * 0.95 / 2000));
+    expect(calibration.calibrated_alpha).toBeNull();
+    expect(calibrated.significant_count).toBe(plain.significant_count);
+  });
+
+  it('size-adjusts a liberal test when calibrate_alpha is set', async () => {
+    // Pooled t with the larger variance in the smaller group: actual size about 0.14
+    const results = await runStatisticalSimulation(baseParams({
+      group2_std: 3,
+      group2_sample_size: 10,
+      num_simulations: 2000,
+      self_calibrate: true,
+      calibrate_alpha: true
+    }));
+    const calibration = results.metadata.size_calibration!;
+
+    expect(calibration.estimated_size).toBeGreaterThan(0.08);
+    expect(calibration.calibrated_alpha!).toBeLessThan(0.05);
+    expect(results.significant_count)
+      .toBe(results.individual_results.filter(r => r.p_value < calibration.calibrated_alpha!).length);
+  });
+});
+
+describe('multi-pair random_seed', () => {
+  const pair = (id: string) => ({
+    id,
+    name: id,
+    group1: { mean: 0.5, std: 1 },
+    group2: { mean: 0, std: 1 },
+    sample_size_per_group: 20,
+    enabled: true
+  });
+  const multiParams = (random_seed?: number): MultiPairSimulationParams => ({
+    pairs: [pair('a'), pair('b')],
+    global_settings: {
+      num_simulations: 100,
+      significance_levels: [0.05],
+      confidence_level: 0.95,
+      random_seed,
+      test_type: 'pooled'
+    },
+    ui_preferences: { theme: 'light', decimal_places: 3, chart_animations: false, color_blind_safe: false }
+  });
+
+  it('reproduces every pair from the global seed while identical pairs stay independent', async () => {
+    const engine = new MultiPairSimulationEngine();
+    const first = await engine.runMultiPairSimulation(multiParams(7));
+    const second = await engine.runMultiPairSimulation(multiParams(7));
+
+    expect(second.pairs_results.map(r => r.individual_results))
+      .toEqual(first.pairs_results.map(r => r.individual_results));
+    expect(first.pairs_results[1].individual_results).not.toEqual(first.pairs_results[0].individual_results);
+  });
+
+  it('gives different runs under different seeds', async () => {
+    const engine = new MultiPairSimulationEngine();
+    const [first, second] = await Promise.all([
+      engine.runMultiPairSimulation(multiParams(7)),
+      engine.runMultiPairSimulation(multiParams(8))
+    ]);
+    expect(second.pairs_results[0].individual_results).not.toEqual(first.pairs_results[0].individual_results);
+  });
+});
+
+describe('effect_size_class_counts', () => {
+  it('bands every simulated effect size', async () => {
+    const results = await runStatisticalSimulation(baseParams({ effect_size_thresholds: [0.1, 0.3, 0.6] }));
+    const counts = results.effect_size_class_counts!;
+    expect(counts.negligible + counts.small + counts.medium + counts.large).toBe(500);
+    expect(counts.large)
+      .toBe(results.individual_results.filter(r => Math.abs(r.effect_size) >= 0.6).length);
+  });
+
+  it('rejects bands that do not increase', async () => {
+    await expect(runStatisticalSimulation(baseParams({ effect_size_thresholds: [0.2, 0.2, 0.8] })))
+      .rejects.toThrow('thresholds');
+  });
+
+  it('leaves Mann-Whitney results unbanded unless rank-biserial thresholds are given', async () => {
+    const unbanded = await runStatisticalSimulation(baseParams({ test_type: 'mann_whitney' }));
+    expect(unbanded.effect_size_class_counts).toBeUndefined();
+
+    const banded = await runStatisticalSimulation(baseParams({
+      test_type: 'mann_whitney',
+      effect_size_thresholds: [0.1, 0.3, 0.5]
+    }));
+    const counts = banded.effect_size_class_counts!;
+    expect(counts.negligible + counts.small + counts.medium + counts.large).toBe(500);
+    expect(counts.large)
+      .toBe(banded.individual_results.filter(r => Math.abs(r.effect_size) >= 0.5).length);
+  });
+});
+
+describe('Type M and Type S errors', () => {
+  it('are both substantial at low power', async () => {
+    // d = 0.1 with n = 10 per group: power about 0.05. Normal approximation: a significant
+    // estimate has |d| > 0.88, so Type M is near 10, and about a quarter have the wrong sign
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0.1,
+      sample_size_per_group: 10,
+      num_simulations: 4000
+    }));
+    expect(results.type_m_error!).toBeGreaterThan(5);
+    expect(results.type_s_error!).toBeGreaterThan(0.1);
+    expect(results.type_s_error!).toBeLessThan(0.45);
+  });
+
+  it('vanish at high power', async () => {
+    // d = 1 with n = 50 per group: power above 0.999, and E[d] = 1 / J(98) ~ 1.008
+    const results = await runStatisticalSimulation(baseParams({ group1_mean: 1, sample_size_per_group: 50 }));
+    expect(results.type_m_error!).toBeGreaterThan(0.97);
+    expect(results.type_m_error!).toBeLessThan(1.05);
+    expect(results.type_s_error).toBe(0);
+  });
+
+  it('are null when the true effect is zero', async () => {
+    const results = await runStatisticalSimulation(baseParams({ group1_mean: 0 }));
+    expect(results.type_m_error).toBeNull();
+    expect(results.type_s_error).toBeNull();
+  });
+});
+
+// Stands in for the chunk worker: clones messages both ways as postMessage would, and
+// answers later chunks first so the main thread has to restore index order
+class InProcessWorker {
+  static created = 0;
+  onmessage: ((e: { data: unknown }) => void) | null = null;
+  onerror: ((e: { message: string }) => void) | null = null;
+  private readonly order = InProcessWorker.created++;
+
+  postMessage(message: { type: 'RANGE'; setup: IterationSetup; start: number; end: number }) {
+    const { setup, start, end } = structuredClone(message);
+    setTimeout(() => this.onmessage?.({
+      data: { type: 'SUCCESS', records: structuredClone(simulateRange(setup, start, end)) }
+    }), 40 - 10 * this.order);
+  }
+
+  terminate() {}
+}
+
+describe('parallel_workers', () => {
+  afterEach(() => {
+    vi.unstubAllGlobals();
+  });
+
+  it('folds worker chunks into exactly the serial results', async () => {
+    InProcessWorker.created = 0;
+    vi.stubGlobal('Worker', InProcessWorker);
+    const parallel = await runStatisticalSimulation(baseParams({ parallel_workers: 4 }));
+    vi.unstubAllGlobals();
+    const serial = await runStatisticalSimulation(baseParams());
+
+    expect(InProcessWorker.created).toBe(4);
+    expect(parallel.individual_results).toEqual(serial.individual_results);
+    expect(parallel.p_value_histogram).toEqual(serial.p_value_histogram);
+    expect(parallel.effect_size_ci).toEqual(serial.effect_size_ci);
+    expect(parallel.metadata.warnings).toEqual(serial.metadata.warnings);
+  });
+
+  it('rejects a non-integer worker count', async () => {
+    await expect(runStatisticalSimulation(baseParams({ parallel_workers: 1.5 }))).rejects.toThrow(SimulationError);
+  });
+});
+
+describe('p_value_histogram_by_source', () => {
+  it('splits each p-value bin between null and alternative simulations', async () => {
+    const results = await runStatisticalSimulation(baseParams({ null_fraction: 0.3, num_simulations: 2000 }));
+    const bins = results.p_value_histogram_by_source!;
+
+    expect(bins).toHaveLength(results.p_value_histogram.length);
+    bins.forEach((bin, k) => expect(bin.null_count + bin.alt_count).toBe(results.p_value_histogram[k].count));
+
+    const nulls = bins.reduce((sum, b) => sum + b.null_count, 0);
+    expect(Math.abs(nulls - 600)).toBeLessThan(4 * Math.sqrt(2000 * 0.3 * 0.7));
+    // Null p-values are uniform; the alternatives pile up in the first bin (power about 0.34)
+    expect(bins[0].null_count / nulls).toBeLessThan(0.1);
+    expect(bins[0].alt_count / (2000 - nulls)).toBeGreaterThan(0.2);
+  });
+
+  it('is absent without a null fraction', async () => {
+    expect((await runStatisticalSimulation(baseParams({ num_simulations: 10 }))).p_value_histogram_by_source).toBeUndefined();
+  });
+});
+
+describe('unequal group sizes', () => {
+  it('uses both sizes for the df and the analytic CI', async () => {
+    const results = await runStatisticalSimulation(baseParams({ group2_sample_size: 40, num_simulations: 50 }));
+    const width = StatisticalUtils.effectSizeCIWidth(0.5, 20, 40);
+    results.individual_results.forEach(r => {
+      expect(r.degrees_of_freedom).toBe(58);
+      expect(r.confidence_interval[1] - r.confidence_interval[0]).toBeCloseTo(width, 10);
+    });
+  });
+
+  it('rejects a group 2 size below 2', async () => {
+    await expect(runStatisticalSimulation(baseParams({ group2_sample_size: 1 }))).rejects.toThrow('sample size');
+  });
+});
+
+describe('Mann-Whitney test mode', () => {
+  it('reports the rank-biserial r, centred on 2 Phi(d / sqrt(2)) - 1', async () => {
+    const results = await runStatisticalSimulation(baseParams({ test_type: 'mann_whitney', num_simulations: 1000 }));
+    results.individual_results.forEach(r => {
+      expect(Math.abs(r.effect_size)).toBeLessThanOrEqual(1);
+      expect(r.t_statistic).toBeUndefined();
+    });
+    // U / (n1 n2) is unbiased for P(X > Y), so mean r sits at 2 Phi(0.5 / sqrt(2)) - 1 = 0.2763
+    expect(Math.abs(results.mean_effect_size - 0.276326)).toBeLessThan(0.03);
+    // Coverage is judged on the same r scale
+    expect(results.ci_coverage).toBeGreaterThan(0.9);
+  });
+
+  it('holds its size under the null', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      test_type: 'mann_whitney',
+      num_simulations: 2000
+    }));
+    expect(Math.abs(results.significant_count / 2000 - 0.05)).toBeLessThan(4 * Math.sqrt(0.05 * 0.95 / 2000));
+  });
+
+  it('rejects CI methods built for Cohen\'s d', async () => {
+    await expect(runStatisticalSimulation(baseParams({
+      test_type: 'mann_whitney',
+      ci_method: { type: 'bootstrap_t', n_boot: 100 }
+    }))).rejects.toThrow();
+  });
+});
+
+describe('low-df warning', () => {
+  it('flags runs with fewer than 3 df unless min_df lifts them', async () => {
+    const tiny = await runStatisticalSimulation(baseParams({ sample_size_per_group: 2, num_simulations: 20 }));
+    expect(tiny.metadata.warnings.some(w => w.startsWith('20 of 20 simulations used fewer than 3'))).toBe(true);
+
+    const floored = await runStatisticalSimulation(baseParams({ sample_size_per_group: 2, num_simulations: 20, min_df: 5 }));
+    expect(floored.metadata.warnings).toEqual([]);
+    floored.individual_results.forEach(r => expect(r.degrees_of_freedom).toBe(5));
+  });
+});
+
+describe('paired test mode', () => {
+  it('reports d_z, whose truth is d / sqrt(2) for independently drawn groups', async () => {
+    const results = await runStatisticalSimulation(baseParams({ test_type: 'paired', num_simulations: 1000 }));
+    results.individual_results.forEach(r => expect(r.degrees_of_freedom).toBe(19));
+    expect(Math.abs(results.mean_effect_size - 0.5 / Math.SQRT2)).toBeLessThan(0.05);
+    expect(results.ci_coverage).toBeGreaterThan(0.9);
+  });
+});
+
+describe('one-sided alternatives', () => {
+  it('gain power in the true direction and lose it in the other', async () => {
+    const two_sided = await runStatisticalSimulation(baseParams({ num_simulations: 1000 }));
+    const greater = await runStatisticalSimulation(baseParams({ num_simulations: 1000, alternative: 'greater' }));
+    const less = await runStatisticalSimulation(baseParams({ num_simulations: 1000, alternative: 'less' }));
+    expect(greater.significant_count).toBeGreaterThan(two_sided.significant_count);
+    expect(less.significant_count / 1000).toBeLessThan(0.01);
+  });
+
+  it('hold their size under the null', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0,
+      alternative: 'greater',
+      num_simulations: 2000
+    }));
+    expect(Math.abs(results.significant_count / 2000 - 0.05)).toBeLessThan(4 * Math.sqrt(0.05 * 0.95 / 2000));
+  });
+});
+
+describe('wrong_direction_rate', () => {
+  it('equals the Type S rate when every simulation carries the effect', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0.1,
+      sample_size_per_group: 10,
+      num_simulations: 2000
+    }));
+    expect(results.wrong_direction_rate!).toBeGreaterThan(0);
+    expect(results.wrong_direction_rate).toBe(results.type_s_error);
+  });
+
+  it('is diluted by significant null simulations in mixed mode', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      group1_mean: 0.1,
+      sample_size_per_group: 10,
+      null_fraction: 0.5,
+      num_simulations: 2000
+    }));
+    expect(results.wrong_direction_rate!).toBeLessThan(results.type_s_error!);
+  });
+
+  it('is null like type_s_error when every true effect is zero', async () => {
+    const results = await runStatisticalSimulation(baseParams({ group1_mean: 0, hypothesized_effect_size: 0 }));
+    expect(results.significant_count).toBeGreaterThan(0);
+    expect(results.type_s_error).toBeNull();
+    expect(results.wrong_direction_rate).toBeNull();
+  });
+});
+
+describe('histogram_bins', () => {
+  it('sets the number of p-value bins', async () => {
+    const results = await runStatisticalSimulation(baseParams({ histogram_bins: 5 }));
+    expect(results.p_value_histogram).toHaveLength(5);
+    expect(results.p_value_histogram[1]).toMatchObject({ bin_start: 0.2, bin_end: 0.4 });
+    expect(results.p_value_histogram.reduce((sum, b) => sum + b.count, 0)).toBe(500);
+  });
+
+  it('rejects a zero or non-integer bin count', async () => {
+    await expect(runStatisticalSimulation(baseParams({ histogram_bins: 0 }))).rejects.toThrow('histogram bins');
+    await expect(runStatisticalSimulation(baseParams({ histogram_bins: 2.5 }))).rejects.toThrow('histogram bins');
+  });
+});
+
+describe('effect_size_metric hedges_g', () => {
+  it("averages g, which removes most of d's small-sample bias", async () => {
+    const d = await runStatisticalSimulation(baseParams({ sample_size_per_group: 5, num_simulations: 4000 }));
+    const g = await runStatisticalSimulation(baseParams({
+      sample_size_per_group: 5,
+      num_simulations: 4000,
+      effect_size_metric: 'hedges_g'
+    }));
+    // E[d] is about 0.5 / J = 0.55 at df = 8; g brings it back to 0.5
+    expect(g.mean_effect_size).toBeCloseTo(d.mean_effect_size * (1 - 3 / 31), 12);
+    expect(Math.abs(g.mean_effect_size - 0.5)).toBeLessThan(Math.abs(d.mean_effect_size - 0.5));
+  });
+
+  it('is rejected for the Mann-Whitney test', async () => {
+    await expect(runStatisticalSimulation(baseParams({ test_type: 'mann_whitney', effect_size_metric: 'hedges_g' })))
+      .rejects.toThrow("Hedges' g");
+  });
+});
+
+describe('powerAcrossAlphas', () => {
+  it('reuses one run\'s p-values for every alpha', async () => {
+    const results = await runStatisticalSimulation(baseParams());
+    const curve = powerAcrossAlphas(results, [0.01, 0.05, 0.1]);
+
+    expect(curve.map(point => point.alpha)).toEqual([0.01, 0.05, 0.1]);
+    expect(curve[1].power).toBe(results.significant_count / 500);
+    expect(curve[0].power).toBeLessThanOrEqual(curve[1].power);
+    expect(curve[1].power).toBeLessThanOrEqual(curve[2].power);
+  });
+
+  it('needs stored rows and alphas inside (0, 1)', async () => {
+    const lean = await runStatisticalSimulation(baseParams({ store_individual_results: false }));
+    expect(() => powerAcrossAlphas(lean, [0.05])).toThrow('individual results');
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 10 }));
+    expect(() => powerAcrossAlphas(results, [0])).toThrow('Alphas');
+  });
+});
+
+describe('bootstrap_percentile CI method', () => {
+  it('covers the true effect near the nominal rate', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      ci_method: { type: 'bootstrap_percentile', n_boot: 400 },
+      num_simulations: 300
+    }));
+    // Percentile intervals for d run slightly short at n = 20
+    expect(results.ci_coverage).toBeGreaterThan(0.88);
+    expect(results.ci_coverage).toBeLessThan(0.99);
+  });
+});
+
+describe('bootstrap Monte Carlo error diagnostics', () => {
+  it('reports the average MC error of each endpoint, shrinking with more resamples', async () => {
+    const run = (n_boot: number) => runStatisticalSimulation(baseParams({
+      ci_method: { type: 'bootstrap_percentile', n_boot },
+      diagnostics: true,
+      num_simulations: 40
+    }));
+    const coarse = (await run(200)).bootstrap_mc_error!;
+    const fine = (await run(1600)).bootstrap_mc_error!;
+
+    expect(coarse.mean_lower_mc_error!).toBeGreaterThan(0);
+    expect(fine.mean_lower_mc_error!).toBeLessThan(coarse.mean_lower_mc_error!);
+    expect(fine.mean_upper_mc_error!).toBeLessThan(coarse.mean_upper_mc_error!);
+  });
+
+  it('is absent without diagnostics', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      ci_method: { type: 'bootstrap_percentile', n_boot: 100 },
+      num_simulations: 10
+    }));
+    expect(results.bootstrap_mc_error).toBeUndefined();
+  });
+});
+
+describe('worker fallback', () => {
+  afterEach(() => {
+    vi.unstubAllGlobals();
+  });
+
+  it('runs serially with a warning when workers cannot be created', async () => {
+    vi.stubGlobal('Worker', class {
+      constructor() {
+        throw new Error('Workers are disabled');
+      }
+    });
+    const fallback = await runStatisticalSimulation(baseParams({ parallel_workers: 4 }));
+    vi.unstubAllGlobals();
+    const serial = await runStatisticalSimulation(baseParams());
+
+    expect(fallback.individual_results).toEqual(serial.individual_results);
+    expect(fallback.metadata.warnings).toEqual([
+      'Parallel workers were unavailable (Workers are disabled); simulations from index 0 ran serially'
+    ]);
+  });
+
+  it('falls back when a worker script fails to load', async () => {
+    vi.stubGlobal('Worker', class {
+      onerror: ((e: { message: string }) => void) | null = null;
+      postMessage() {
+        setTimeout(() => this.onerror?.({ message: 'Failed to load worker script' }), 0);
+      }
+      terminate() {}
+    });
+    const fallback = await runStatisticalSimulation(baseParams({ parallel_workers: 2, num_simulations: 100 }));
+    vi.unstubAllGlobals();
+    const serial = await runStatisticalSimulation(baseParams({ num_simulations: 100 }));
+
+    expect(fallback.individual_results).toEqual(serial.individual_results);
+    expect(fallback.metadata.warnings[0]).toMatch(/^Parallel workers were unavailable \(Failed to load worker script\)/);
+  });
+});
+
+describe('onProgress', () => {
+  it('reports about every 1% of simulations and always the last one', async () => {
+    const progress: { completed: number; total: number }[] = [];
+    await runStatisticalSimulation(baseParams({ num_simulations: 250 }), { onProgress: p => progress.push(p) });
+
+    // Step ceil(250 / 100) = 3: 3, 6, ..., 249, then 250
+    expect(progress).toHaveLength(84);
+    expect(progress[0]).toEqual({ completed: 3, total: 250 });
+    expect(progress[progress.length - 1]).toEqual({ completed: 250, total: 250 });
+    progress.slice(1).forEach((p, k) => expect(p.completed).toBeGreaterThan(progress[k].completed));
+  });
+
+  it('reaches the multi-pair progress callback tagged with its pair', async () => {
+    const engine = new MultiPairSimulationEngine();
+    const phases: string[] = [];
+    const completions: number[] = [];
+    await engine.runMultiPairSimulation({
+      pairs: [{
+        id: 'a',
+        name: 'Pair A',
+        group1: { mean: 0.5, std: 1 },
+        group2: { mean: 0, std: 1 },
+        sample_size_per_group: 20,
+        enabled: true
+      }],
+      global_settings: {
+        num_simulations: 100,
+        significance_levels: [0.05],
+        confidence_level: 0.95,
+        random_seed: 1,
+        test_type: 'pooled'
+      },
+      ui_preferences: { theme: 'light', decimal_places: 3, chart_animations: false, color_blind_safe: false }
+    }, p => {
+      phases.push(p.phase);
+      if (p.pairName === 'Pair A' && p.currentSimulation > 0) completions.push(p.currentSimulation);
+    });
+
+    expect(completions[completions.length - 1]).toBe(100);
+    expect(phases[phases.length - 1]).toBe('analyzing_results');
+  });
+});
+
+describe('analyzePaired', () => {
+  // Student's sleep data treated as two occasions on the same ten patients
+  const before = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+  const after = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+
+  it('matches the paired t-test, correlation and d_z of R', () => {
+    // R: t.test(after, before, paired = TRUE); cor(before, after)
+    const result = analyzePaired(before, after);
+    expect(result.n).toBe(10);
+    expect(result.t_statistic).toBeCloseTo(4.0621, 4);
+    expect(result.df).toBe(9);
+    expect(result.p_value).toBeCloseTo(0.002833, 5);
+    expect(result.correlation).toBeCloseTo(0.795170, 5);
+    expect(result.dz).toBeCloseTo(1.284558, 5);
+  });
+
+  it('rejects empty, unequal or non-finite data', () => {
+    expect(() => analyzePaired([], [])).toThrow('must not be empty');
+    expect(() => analyzePaired(before, after.slice(1))).toThrow('equal lengths');
+    expect(() => analyzePaired([1, NaN], [2, 3])).toThrow('finite');
+  });
+});
+
+describe('power', () => {
+  it('is close to 1 with its Wilson interval for a large true effect', async () => {
+    const results = await runStatisticalSimulation(baseParams({ group1_mean: 2, hypothesized_effect_size: 2 }));
+    expect(results.power).toBe(results.significant_count / results.total_count);
+    expect(results.power).toBeGreaterThan(0.99);
+    const [lower, upper] = results.power_wilson_ci;
+    expect(lower).toBeLessThanOrEqual(results.power);
+    expect(upper).toBeGreaterThanOrEqual(results.power);
+    expect(lower).toBeGreaterThan(0.98);
+  });
+});
+
+describe('scatter_sample_size', () => {
+  it('caps the scatter sample with points drawn from the run, reproducibly', async () => {
+    const params = baseParams({ scatter_sample_size: 50 });
+    const first = await runStatisticalSimulation(params);
+    const second = await runStatisticalSimulation(params);
+
+    expect(first.scatter_sample).toHaveLength(50);
+    expect(second.scatter_sample).toEqual(first.scatter_sample);
+    const points = new Set(first.individual_results.map(r => `${r.effect_size},${r.p_value}`));
+    first.scatter_sample!.forEach(([d, p]) => expect(points.has(`${d},${p}`)).toBe(true));
+    // Not simply the first 50 simulations
+    expect(first.scatter_sample!.some(([d]) => first.individual_results.slice(0, 50).every(r => r.effect_size !== d)))
+      .toBe(true);
+  });
+
+  it('keeps every point when the run is smaller than the cap and omits the field by default', async () => {
+    expect((await runStatisticalSimulation(baseParams({ scatter_sample_size: 1000 }))).scatter_sample).toHaveLength(500);
+    expect((await runStatisticalSimulation(baseParams())).scatter_sample).toBeUndefined();
+  });
+
+  it('rejects a negative or fractional cap', async () => {
+    await expect(runStatisticalSimulation(baseParams({ scatter_sample_size: 2.5 }))).rejects.toThrow('Scatter sample size');
+  });
+});
+
+describe('ciWidthCurve', () => {
+  it('narrows monotonically with n and agrees with the analytic width', () => {
+    const sample_sizes = [5, 10, 20, 50, 100, 500];
+    const curve = ciWidthCurve(0.5, sample_sizes);
+
+    expect(curve.map(point => point.n)).toEqual(sample_sizes);
+    curve.slice(1).forEach((point, k) => expect(point.expected_width).toBeLessThan(curve[k].expected_width));
+    // 2 * qt(0.975, 38) * sqrt(1/20 + 1/20)
+    expect(curve[2].expected_width).toBeCloseTo(1.280339, 5);
+  });
+
+  it('rejects an empty list and sample sizes below 2', () => {
+    expect(() => ciWidthCurve(0.5, [])).toThrow('At least one sample size');
+    expect(() => ciWidthCurve(0.5, [1, 10])).toThrow('at least 2');
+    expect(() => ciWidthCurve(0.5, [10.5])).toThrow('integers');
+    expect(() => ciWidthCurve(0.5, [10], 1)).toThrow('Confidence level');
+  });
+});
+
+describe('requiredSampleSize', () => {
+  it('matches the textbook per-group sizes', () => {
+    // pwr.t.test(d = 0.5, power = 0.8): n = 63.77; d = 0.8: n = 25.52; one-sided d = 0.5: n = 50.15
+    expect(requiredSampleSize(0.5, 0.05, 0.8)).toBe(64);
+    expect(requiredSampleSize(0.8, 0.05, 0.8)).toBe(26);
+    expect(requiredSampleSize(0.5, 0.05, 0.8, 'greater')).toBe(51);
+  });
+
+  it('returns the smallest n reaching the target', () => {
+    const n = requiredSampleSize(0.5, 0.05, 0.9);
+    expect(StatisticalUtils.analyticPower(0.5, n, 0.05)).toBeGreaterThanOrEqual(0.9);
+    expect(StatisticalUtils.analyticPower(0.5, n - 1, 0.05)).toBeLessThan(0.9);
+  });
+
+  it('explains when no searched n reaches the target', () => {
+    expect(() => requiredSampleSize(0.5, 0.05, 0.8, 'less')).toThrow('is not reached');
+    expect(() => requiredSampleSize(0.5, 0.05, 1)).toThrow('Target power');
+  });
+});
+
+describe('multiple_comparison', () => {
+  it('orders Bonferroni <= Benjamini-Hochberg <= raw and leaves the per-result flags alone', async () => {
+    const count = async (multiple_comparison: MultipleComparison) => {
+      const results = await runStatisticalSimulation(baseParams({ multiple_comparison }));
+      expect(results.significant_count).toBe(results.individual_results.filter(r => r.significant).length);
+      return results;
+    };
+    const raw = await count('none');
+    const bonferroni = await count('bonferroni');
+    const bh = await count('benjamini_hochberg');
+
+    expect(raw.adjusted_significant_count).toBe(raw.significant_count);
+    expect(bonferroni.significant_count).toBe(raw.significant_count);
+    expect(bonferroni.adjusted_significant_count).toBeLessThanOrEqual(bh.adjusted_significant_count!);
+    expect(bh.adjusted_significant_count).toBeLessThanOrEqual(raw.adjusted_significant_count!);
+    expect(bonferroni.adjusted_significant_count).toBeLessThan(raw.adjusted_significant_count!);
+  });
+});
+
+describe('pooled_hedges_g', () => {
+  it('is less biased than the mean Cohen d at small n', async () => {
+    // n = 4 per group: E[d] = 0.5 / J(6) = 0.575, while g is unbiased
+    const results = await runStatisticalSimulation(baseParams({ sample_size_per_group: 4, num_simulations: 5000 }));
+    const pooled = results.pooled_hedges_g!;
+
+    expect(Math.abs(pooled.estimate - 0.5)).toBeLessThan(Math.abs(results.mean_effect_size - 0.5));
+    expect(Math.abs(pooled.estimate - 0.5)).toBeLessThan(0.05);
+    expect(pooled.ci[0]).toBeLessThan(pooled.estimate);
+    expect(pooled.ci[1]).toBeGreaterThan(pooled.estimate);
+  });
+
+  it('gives the standard error of the mean g', async () => {
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 2000 }));
+    const g = results.individual_results.map(r => r.hedges_g!);
+    const mean = g.reduce((sum, x) => sum + x, 0) / g.length;
+    const sd = Math.sqrt(g.reduce((sum, x) => sum + (x - mean) ** 2, 0) / (g.length - 1));
+
+    expect(results.pooled_hedges_g!.estimate).toBeCloseTo(mean, 10);
+    expect(results.pooled_hedges_g!.se / (sd / Math.sqrt(g.length))).toBeGreaterThan(0.9);
+    expect(results.pooled_hedges_g!.se / (sd / Math.sqrt(g.length))).toBeLessThan(1.1);
+  });
+
+  it('is left out for Mann-Whitney runs', async () => {
+    expect((await runStatisticalSimulation(baseParams({ test_type: 'mann_whitney' }))).pooled_hedges_g).toBeUndefined();
+  });
+});
+
+describe('runSimulationBatch seeding', () => {
+  const sets = [baseParams({ num_simulations: 300 }), baseParams({ num_simulations: 300, group1_mean: 0.6 })];
+  const correlation = (a: AggregatedResults, b: AggregatedResults) => {
+    const x = a.individual_results.map(r => r.effect_size);
+    const y = b.individual_results.map(r => r.effect_size);
+    const mean = (v: number[]) => v.reduce((sum, value) => sum + value, 0) / v.length;
+    const mx = mean(x);
+    const my = mean(y);
+    const cov = x.reduce((sum, value, i) => sum + (value - mx) * (y[i] - my), 0);
+    const ss = (v: number[], m: number) => v.reduce((sum, value) => sum + (value - m) ** 2, 0);
+    return cov / Math.sqrt(ss(x, mx) * ss(y, my));
+  };
+
+  it('shares the sampling noise across sets by default', async () => {
+    const [first, second] = await runSimulationBatch(sets, undefined, 99);
+    expect(correlation(first, second)).toBeGreaterThan(0.9);
+    expect(second.mean_effect_size).toBeGreaterThan(first.mean_effect_size);
+  });
+
+  it('derives base_seed + i per set', async () => {
+    const [, second] = await runSimulationBatch(sets, 'per_index', 99);
+    const direct = await runStatisticalSimulation({ ...sets[1], seed: 100 });
+    expect(second.individual_results).toEqual(direct.individual_results);
+  });
+
+  it('draws uncorrelated sets when independent', async () => {
+    const [first, second] = await runSimulationBatch(sets, 'independent', 99);
+    expect(Math.abs(correlation(first, second))).toBeLessThan(0.25);
+  });
+});
+
+describe('CLES', () => {
+  it('brackets each simulation and centres on 0.5 without an effect', async () => {
+    const results = await runStatisticalSimulation(baseParams({ group1_mean: 0, hypothesized_effect_size: 0 }));
+    results.individual_results.forEach(r => {
+      expect(r.cles_ci[0]).toBeLessThanOrEqual(r.cles);
+      expect(r.cles_ci[1]).toBeGreaterThanOrEqual(r.cles);
+    });
+    // sd(CLES) ~ 0.09, so over 500 simulations the mean has a standard error of about 0.004
+    expect(Math.abs(results.mean_cles - 0.5)).toBeLessThan(0.02);
+    expect(results.cles_ci[0]).toBeLessThan(0.5);
+    expect(results.cles_ci[1]).toBeGreaterThan(0.5);
+  });
+
+  it('takes cles_ci from the per-simulation cles whatever the effect-size metric', async () => {
+    const results = await runStatisticalSimulation(baseParams({ effect_size_metric: 'hedges_g' }));
+    const sorted = results.individual_results.map(r => r.cles).sort((a, b) => a - b);
+    expect(results.cles_ci).toEqual([sorted[12], sorted[487]]);
+    // Mapping the Hedges' g interval would sit on a shrunken scale below the CLES percentiles
+    expect(StatisticalUtils.commonLanguageEffectSize(results.effect_size_ci[1])).toBeLessThan(results.cles_ci[1]);
+
+    const streamed = await runStatisticalSimulation(baseParams({
+      effect_size_metric: 'hedges_g',
+      store_individual_results: false
+    }));
+    expect(Math.abs(streamed.cles_ci[0] - results.cles_ci[0])).toBeLessThan(0.02);
+    expect(Math.abs(streamed.cles_ci[1] - results.cles_ci[1])).toBeLessThan(0.02);
+  });
+});
+
+describe('power_trace', () => {
+  it('records the running power at half-decade counts and ends at the reported power', async () => {
+    const results = await runStatisticalSimulation(baseParams({ power_trace: true }));
+    const trace = results.power_trace!;
+
+    expect(trace.map(([count]) => count)).toEqual([10, 32, 100, 316, 500]);
+    expect(trace[trace.length - 1][1]).toBe(results.power);
+    const flags = results.individual_results.map(r => r.significant);
+    trace.forEach(([count, power]) => expect(power).toBe(flags.slice(0, count).filter(Boolean).length / count));
+  });
+
+  it('does not repeat a final count that lands on the grid', async () => {
+    const results = await runStatisticalSimulation(baseParams({ power_trace: true, num_simulations: 100 }));
+    expect(results.power_trace!.map(([count]) => count)).toEqual([10, 32, 100]);
+  });
+
+  it('is left out unless requested', async () => {
+    expect((await runStatisticalSimulation(baseParams())).power_trace).toBeUndefined();
+  });
+});
+
+describe('ci_confidence_level', () => {
+  it('widens every interval at 99% against 90% on the same data', async () => {
+    const narrow = await runStatisticalSimulation(baseParams({ ci_confidence_level: 0.9 }));
+    const wide = await runStatisticalSimulation(baseParams({ ci_confidence_level: 0.99 }));
+
+    wide.individual_results.forEach((r, i) => {
+      expect(r.effect_size).toBe(narrow.individual_results[i].effect_size);
+      expect(r.confidence_interval[0]).toBeLessThan(narrow.individual_results[i].confidence_interval[0]);
+      expect(r.confidence_interval[1]).toBeGreaterThan(narrow.individual_results[i].confidence_interval[1]);
+    });
+    expect(wide.mean_ci_width).toBeGreaterThan(narrow.mean_ci_width);
+  });
+
+  it('moves the coverage to the chosen level', async () => {
+    // sqrt(0.9 * 0.1 / 500) ~ 0.013; sqrt(0.99 * 0.01 / 500) ~ 0.0045
+    const narrow = await runStatisticalSimulation(baseParams({ ci_confidence_level: 0.9 }));
+    const wide = await runStatisticalSimulation(baseParams({ ci_confidence_level: 0.99 }));
+    expect(Math.abs(narrow.ci_coverage - 0.9)).toBeLessThan(0.055);
+    expect(Math.abs(wide.ci_coverage - 0.99)).toBeLessThan(0.02);
+  });
+
+  it('rejects levels outside (0, 1)', async () => {
+    await expect(runStatisticalSimulation(baseParams({ ci_confidence_level: 1 }))).rejects.toThrow('CI confidence level');
+  });
+});
+
+describe('bayes_factor', () => {
+  it('is only computed on request', async () => {
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 20 }));
+    expect(results.individual_results.every(r => r.bayes_factor === undefined)).toBe(true);
+  });
+
+  it('is the JZS factor of each simulation t statistic', async () => {
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 20, bayes_factor: true }));
+    results.individual_results.forEach(r => {
+      expect(r.bayes_factor).toBeCloseTo(StatisticalUtils.jzsBayesFactor(r.t_statistic!, 20, 20), 10);
+    });
+  });
+
+  it('uses the pooled t implied by d for Welch runs', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      num_simulations: 20,
+      test_type: 'welch',
+      group2_sample_size: 40,
+      group2_std: 2,
+      bayes_factor: true
+    }));
+    results.individual_results.forEach(r => {
+      const pooled_t = r.effect_size * Math.sqrt(20 * 40 / 60);
+      expect(r.bayes_factor).toBeCloseTo(StatisticalUtils.jzsBayesFactor(pooled_t, 20, 40), 10);
+    });
+  });
+
+  it('is left out for Mann-Whitney runs', async () => {
+    const results = await runStatisticalSimulation(baseParams({
+      num_simulations: 20,
+      test_type: 'mann_whitney',
+      bayes_factor: true
+    }));
+    expect(results.individual_results.every(r => r.bayes_factor === undefined)).toBe(true);
+  });
+});
+
+describe('effect_size_metric glass_delta', () => {
+  it("differs from Cohen's d when the SDs differ", async () => {
+    const unequal = { group1_std: 2, test_type: 'welch' as const };
+    const cohen = await runStatisticalSimulation(baseParams(unequal));
+    const glass = await runStatisticalSimulation(baseParams({ ...unequal, effect_size_metric: 'glass_delta' }));
+    const glass_control_1 = await runStatisticalSimulation(
+      baseParams({ ...unequal, effect_size_metric: 'glass_delta', glass_control_group: 1 }));
+
+    // d = 0.5 / sqrt((4 + 1) / 2) ~ 0.32; delta = 0.5 / 1 with group 2 as control, 0.5 / 2 with group 1
+    expect(Math.abs(cohen.mean_effect_size - 0.32)).toBeLessThan(0.06);
+    expect(Math.abs(glass.mean_effect_size - 0.5)).toBeLessThan(0.1);
+    expect(Math.abs(glass_control_1.mean_effect_size - 0.25)).toBeLessThan(0.05);
+    expect(glass.individual_results.map(r => r.p_value)).toEqual(cohen.individual_results.map(r => r.p_value));
+  });
+});
+
+describe('ci_method noncentral_t', () => {
+  it('reaches nominal coverage at small n', async () => {
+    // n = 5 per group; four binomial standard errors around 0.95 at 1000 simulations is 0.028
+    const results = await runStatisticalSimulation(baseParams({
+      sample_size_per_group: 5,
+      num_simulations: 1000,
+      ci_method: { type: 'noncentral_t' }
+    }));
+    results.individual_results.forEach(r =>
+      expect(r.confidence_interval).toEqual(StatisticalUtils.noncentralTCI(r.effect_size, 5, 5)));
+    expect(Math.abs(results.ci_coverage - 0.95)).toBeLessThan(0.028);
+  });
+});
+
+describe('effect_size_std and p_value_std', () => {
+  afterEach(() => {
+    vi.restoreAllMocks();
+  });
+
+  it('are the sample standard deviations of the per-simulation values', async () => {
+    const results = await runStatisticalSimulation(baseParams());
+    const sd = (values: number[]) => {
+      const mean = values.reduce((sum, v) => sum + v, 0) / values.length;
+      return Math.sqrt(values.reduce((sum, v) => sum + (v - mean) ** 2, 0) / (values.length - 1));
+    };
+
+    expect(results.effect_size_std).toBeGreaterThan(0);
+    expect(results.effect_size_std).toBeCloseTo(sd(results.individual_results.map(r => r.effect_size)), 10);
+    expect(results.p_value_std).toBeGreaterThan(0);
+    expect(results.p_value_std).toBeCloseTo(sd(results.individual_results.map(r => r.p_value)), 10);
+  });
+
+  it('are zero when every simulation gives the same test result', async () => {
+    const fixed = StatisticalUtils.twoSampleTTest([1, 2, 3, 4], [0, 1, 2, 2]);
+    vi.spyOn(StatisticalUtils, 'twoSampleTTest').mockReturnValue(fixed);
+
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 50 }));
+    expect(results.effect_size_std).toBe(0);
+    expect(results.p_value_std).toBeCloseTo(0, 12);
+  });
+});
+
+describe('runSampleSizeSweep', () => {
+  const sample_sizes = [10, 20, 40, 80];
+
+  it('gives power rising with n, near the analytic value, reproducibly', async () => {
+    const sweep = await runSampleSizeSweep(baseParams(), sample_sizes);
+    expect(sweep.map(point => point.n)).toEqual(sample_sizes);
+    sweep.slice(1).forEach((point, k) => expect(point.power).toBeGreaterThanOrEqual(sweep[k].power));
+    sweep.forEach(point => {
+      const expected = StatisticalUtils.analyticPower(0.5, point.n, 0.05);
+      expect(Math.abs(point.power - expected)).toBeLessThan(4 * Math.sqrt(expected * (1 - expected) / 500));
+    });
+
+    expect(await runSampleSizeSweep(baseParams(), sample_sizes)).toEqual(sweep);
+  });
+
+  it('rejects an empty list and sample sizes below 2', async () => {
+    await expect(runSampleSizeSweep(baseParams(), [])).rejects.toThrow('At least one sample size');
+    await expect(runSampleSizeSweep(baseParams(), [1])).rejects.toThrow('at least 2');
+  });
+});
+
+describe('include_null_calibration', () => {
+  it('reports a null rejection rate close to alpha', async () => {
+    const results = await runStatisticalSimulation(baseParams({ num_simulations: 2000, include_null_calibration: true }));
+    // Four binomial standard errors at 2000 simulations
+    expect(Math.abs(results.null_rejection_rate! - 0.05)).toBeLessThan(4 * Math.sqrt(0.05 * 0.95 / 2000));
+    expect(results.power).toBeGreaterThan(0.2);
+  });
+
+  it('is reproducible under a seed and left out unless requested', async () => {
+    const params = baseParams({ include_null_calibration: true });
+    expect((await runStatisticalSimulation(params)).null_rejection_rate)
+      .toBe((await runStatisticalSimulation(params)).null_rejection_rate);
+    expect((await runStatisticalSimulation(baseParams())).null_rejection_rate).toBeUndefined();
+  });
+});
+
+describe('group contamination', () => {
+  it('shrinks the standardized effect and the power', async () => {
+    const clean = await runStatisticalSimulation(baseParams());
+    const contaminated = await runStatisticalSimulation(baseParams({
+      group1_contamination: { fraction: 0.2, scale: 5 },
+      group2_contamination: { fraction: 0.2, scale: 5 }
+    }));
+    // Outliers inflate the SDs, so the standardized effect shrinks while the mean difference stays
+    expect(contaminated.mean_effect_size).toBeLessThan(clean.mean_effect_size);
+    expect(contaminated.power).toBeLessThan(clean.power);
+  });
+
+  it('rejects an invalid contamination before running', async () => {
+    await expect(runStatisticalSimulation(baseParams({ group2_contamination: { fraction: -0.1, scale: 3 } })))
+      .rejects.toThrow('Group 2 contamination fraction');
+  });
+});
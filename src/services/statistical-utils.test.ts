@@ -0,0 +1,818 @@
+import { describe, it, expect } from 'vitest';
+import { StatisticalUtils } from './multi-pair-simulation';
+import { createSeededRandom } from '../utils/random';
+import { Distribution } from '../types/simulation.types';
+
+const mean = (values: number[]) => values.reduce((sum, v) => sum + v, 0) / values.length;
+const variance = (values: number[]) => {
+  const m = mean(values);
+  return values.reduce((sum, v) => sum + (v - m) ** 2, 0) / (values.length - 1);
+};
+
+// R's built-in sleep data (extra hours of sleep under two drugs), the usual t.test example
+const SLEEP_GROUP1 = [0.7, -1.6, -0.2, -1.2, -0.1, 3.4, 3.7, 0.8, 0.0, 2.0];
+const SLEEP_GROUP2 = [1.9, 0.8, 1.1, 0.1, -0.1, 4.4, 5.5, 1.6, 4.6, 3.4];
+
+describe('twoSampleTTest', () => {
+  it("matches R's Welch t.test(extra ~ group, data = sleep)", () => {
+    // t = -1.8608, df = 17.776, p-value = 0.07939
+    const result = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2, { variance_assumption: 'welch' });
+    expect(result.t_statistic).toBeCloseTo(-1.8608, 4);
+    expect(result.df).toBeCloseTo(17.776, 3);
+    expect(result.p_value).toBeCloseTo(0.07939, 5);
+  });
+
+  it("matches R's pooled t.test(extra ~ group, data = sleep, var.equal = TRUE)", () => {
+    // t = -1.8608, df = 18, p-value = 0.07919
+    const result = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2);
+    expect(result.t_statistic).toBeCloseTo(-1.8608, 4);
+    expect(result.df).toBe(18);
+    expect(result.p_value).toBeCloseTo(0.07919, 5);
+  });
+});
+
+describe('mixture distribution', () => {
+  const mixture: Distribution = {
+    type: 'mixture',
+    components: [
+      { weight: 0.3, mean: -2, std: 1 },
+      { weight: 0.7, mean: 1, std: 0.5 }
+    ]
+  };
+
+  it("has the weighted component means as its empirical mean, offset by the group mean", () => {
+    const samples = StatisticalUtils.generateSamples(100000, 10, 1, mixture, createSeededRandom(209));
+    // 10 + 0.3 * -2 + 0.7 * 1
+    expect(Math.abs(mean(samples) - 10.1)).toBeLessThan(0.02);
+  });
+
+  it('reports the moments it samples from', () => {
+    const moments = StatisticalUtils.populationMoments(10, 1, mixture);
+    const samples = StatisticalUtils.generateSamples(100000, 10, 1, mixture, createSeededRandom(2090));
+    expect(moments.mean).toBeCloseTo(10.1, 12);
+    // 0.3 (1 + 4) + 0.7 (0.25 + 1) - 0.1^2
+    expect(moments.std).toBeCloseTo(Math.sqrt(2.365), 12);
+    expect(Math.abs(Math.sqrt(variance(samples)) - moments.std)).toBeLessThan(0.02);
+  });
+});
+
+describe('buffered normal sampling', () => {
+  it('has the same distribution as per-draw Box-Muller', () => {
+    const n = 100000;
+    const buffered = StatisticalUtils.generateSamples(n, 3, 2, undefined, createSeededRandom(217), true);
+    const per_draw = StatisticalUtils.generateSamples(n, 3, 2, undefined, createSeededRandom(2170), false);
+
+    for (const samples of [buffered, per_draw]) {
+      expect(Math.abs(mean(samples) - 3)).toBeLessThan(0.03);
+      expect(Math.abs(Math.sqrt(variance(samples)) - 2)).toBeLessThan(0.03);
+    }
+    // Matching quartiles: the two paths share a shape, not just two moments
+    const sorted = (values: number[]) => [...values].sort((a, b) => a - b);
+    const [a, b] = [sorted(buffered), sorted(per_draw)];
+    for (const q of [0.05, 0.25, 0.5, 0.75, 0.95]) {
+      expect(Math.abs(StatisticalUtils.percentile(a, q) - StatisticalUtils.percentile(b, q))).toBeLessThan(0.08);
+    }
+  });
+});
+
+describe('Laplace distribution', () => {
+  it('matches the group mean and variance, with the Laplace excess kurtosis of 3', () => {
+    const samples = StatisticalUtils.generateSamples(200000, 5, 2, { type: 'laplace' }, createSeededRandom(225));
+    const m = mean(samples);
+    const v = variance(samples);
+    const kurtosis = mean(samples.map(x => (x - m) ** 4)) / v ** 2 - 3;
+
+    expect(Math.abs(m - 5)).toBeLessThan(0.02);
+    // Scale b = 2 / sqrt(2), variance 2 b^2 = 4
+    expect(Math.abs(v - 4)).toBeLessThan(0.1);
+    expect(Math.abs(kurtosis - 3)).toBeLessThan(0.5);
+  });
+});
+
+describe('permutation test alpha grid', () => {
+  it('enumerates small designs exactly, counting each relabeling with its mirror', () => {
+    // C(6, 3) = 20 relabelings; complete separation is matched only by itself and its mirror
+    const result = StatisticalUtils.permutationTest([4, 5, 6], [1, 2, 3], 1000, createSeededRandom(257));
+    expect(result.exact).toBe(true);
+    expect(result.permutations).toBe(20);
+    expect(result.p_value).toBeCloseTo(2 / 20, 12);
+
+    const levels = StatisticalUtils.achievableAlphaLevels(3, 3, 1000);
+    expect(levels[0]).toBeCloseTo(0.1, 12);
+    expect(levels.some(level => Math.abs(level - result.p_value) < 1e-12)).toBe(true);
+    expect(StatisticalUtils.nearestAchievableAlpha(levels, 0.05)).toBeNull();
+  });
+
+  it('uses the Monte Carlo grid once the relabelings outnumber the permutations', () => {
+    const levels = StatisticalUtils.achievableAlphaLevels(10, 10, 999);
+    expect(levels).toHaveLength(1000);
+    expect(levels[0]).toBeCloseTo(1 / 1000, 12);
+    expect(StatisticalUtils.nearestAchievableAlpha(levels, 0.05)).toBeCloseTo(0.05, 12);
+
+    const result = StatisticalUtils.permutationTest([4, 5, 6, 7, 8, 9, 10, 11, 12, 13], [1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 999, createSeededRandom(2570));
+    expect(result.exact).toBe(false);
+    expect(levels.some(level => Math.abs(level - result.p_value) < 1e-12)).toBe(true);
+  });
+
+  it('gives the sign test two-sided binomial tails', () => {
+    // n = 10: P(X <= k) = 1, 11, 56, 176, 386, 638 over 1024
+    const expected = [1, 11, 56, 176, 386].map(c => 2 * c / 1024).concat(1);
+    StatisticalUtils.signTestAlphaLevels(10).forEach((level, k) => expect(level).toBeCloseTo(expected[k], 12));
+  });
+});
+
+describe('permutation test timeout', () => {
+  it('stops past the deadline and takes p from the permutations drawn', () => {
+    const group1 = Array.from({length: 30}, (_, i) => i / 10);
+    const group2 = Array.from({length: 30}, (_, i) => i / 10 - 0.5);
+    const result = StatisticalUtils.permutationTest(group1, group2, 5000, createSeededRandom(262), 'two_sided', performance.now() - 1);
+
+    // The floor of 20 is kept, then the first deadline check (every 16) stops at 32
+    expect(result.permutations).toBe(32);
+    expect(result.exact).toBe(false);
+    const extreme = Math.round(result.p_value * 33) - 1;
+    expect(result.p_value).toBeCloseTo((1 + extreme) / 33, 12);
+  });
+});
+
+describe('effectSizeCIWidth', () => {
+  it('is twice the t-based margin of the analytic interval', () => {
+    // 2 * qt(0.975, 38) * sqrt(1/20 + 1/20)
+    expect(StatisticalUtils.effectSizeCIWidth(0.5, 20, 20)).toBeCloseTo(1.280339, 5);
+    expect(StatisticalUtils.effectSizeCIWidth(0.5, 20, 20, 0.9)).toBeCloseTo(1.066291, 5);
+
+    const [lower, upper] = StatisticalUtils.calculateConfidenceInterval(0.5, 20, 20);
+    expect(upper - lower).toBeCloseTo(StatisticalUtils.effectSizeCIWidth(0.5, 20, 20), 12);
+  });
+
+  it('rejects groups smaller than 2', () => {
+    expect(() => StatisticalUtils.effectSizeCIWidth(0.5, 1, 20)).toThrow();
+  });
+});
+
+describe('isSignificant', () => {
+  const outcome = { p_value: 0.03, effect_size: 0.3, confidence_interval: [0.02, 0.58] as [number, number] };
+
+  it('applies each rule', () => {
+    expect(StatisticalUtils.isSignificant(outcome, 0.05)).toBe(true);
+    expect(StatisticalUtils.isSignificant(outcome, 0.01)).toBe(false);
+    expect(StatisticalUtils.isSignificant(outcome, 0.05, { type: 'ci_excludes_null' })).toBe(true);
+    expect(StatisticalUtils.isSignificant({ ...outcome, confidence_interval: [-0.1, 0.7] }, 0.05, { type: 'ci_excludes_null' })).toBe(false);
+    expect(StatisticalUtils.isSignificant(outcome, 0.05, { type: 'p_value_and_sesoi', sesoi: 0.2 })).toBe(true);
+    expect(StatisticalUtils.isSignificant(outcome, 0.05, { type: 'p_value_and_sesoi', sesoi: 0.4 })).toBe(false);
+    expect(StatisticalUtils.isSignificant(outcome, 0.05, { type: 'ci_excludes_null_and_sesoi', sesoi: 0.4 })).toBe(false);
+  });
+});
+
+describe('pooledStd', () => {
+  it('weights each variance by its degrees of freedom', () => {
+    // (10 * 4 + 20 * 1) / 30 = 2
+    expect(StatisticalUtils.pooledStd(2, 11, 1, 21)).toBeCloseTo(Math.SQRT2, 12);
+
+    const sd = (values: number[]) => Math.sqrt(variance(values));
+    const pooled = StatisticalUtils.pooledStd(sd(SLEEP_GROUP1), 10, sd(SLEEP_GROUP2), 10);
+    expect(pooled).toBeCloseTo(Math.sqrt((variance(SLEEP_GROUP1) + variance(SLEEP_GROUP2)) / 2), 12);
+  });
+
+  it('rejects non-positive SDs and groups smaller than 2', () => {
+    expect(() => StatisticalUtils.pooledStd(0, 10, 1, 10)).toThrow();
+    expect(() => StatisticalUtils.pooledStd(1, 1, 1, 10)).toThrow();
+  });
+});
+
+describe('recommendTest', () => {
+  it("computes the Jarque-Bera statistic from the population moments", () => {
+    // tseries::jarque.bera.test(sleep$extra[1:10]): X-squared = 0.73875, p-value = 0.6912
+    const result = StatisticalUtils.jarqueBeraTest(SLEEP_GROUP1);
+    expect(result.statistic).toBeCloseTo(0.738753, 5);
+    expect(result.p_value).toBeCloseTo(0.691165, 5);
+  });
+
+  // A strict alpha keeps the chance of a spurious rejection on the seeded samples negligible
+  const draw = (seed: number, std: number, distribution?: Distribution) =>
+    StatisticalUtils.generateSamples(200, 0, std, distribution, createSeededRandom(seed));
+
+  it('keeps the pooled t-test for normal data with equal variances', () => {
+    expect(StatisticalUtils.recommendTest(draw(1, 1), draw(2, 1), 0.001).recommended).toBe('pooled');
+  });
+
+  it("switches to Welch's test when the variances differ", () => {
+    expect(StatisticalUtils.recommendTest(draw(3, 1), draw(4, 3), 0.001).recommended).toBe('welch');
+  });
+
+  it('switches to Mann-Whitney for skewed data', () => {
+    const result = StatisticalUtils.recommendTest(draw(5, 1, { type: 'exponential' }), draw(6, 1), 0.001);
+    expect(result.recommended).toBe('mann_whitney');
+    expect(result.reasons[0]).toMatch(/^Group 1 departs from normality/);
+  });
+});
+
+describe('bootstrapTCI', () => {
+  it('interpolates percentiles like R quantile type 7', () => {
+    // quantile(c(1, 2, 4, 8), 0.4) = 2 + 0.2 * 2
+    expect(StatisticalUtils.percentile([1, 2, 4, 8], 0.4)).toBeCloseTo(2.4, 12);
+    expect(StatisticalUtils.percentile([1, 2, 4, 8], 1)).toBe(8);
+  });
+
+  it('brackets the observed d and reproduces under the same stream', () => {
+    const d = StatisticalUtils.cohensD(SLEEP_GROUP1, SLEEP_GROUP2);
+    const a = StatisticalUtils.bootstrapTCI(SLEEP_GROUP1, SLEEP_GROUP2, 999, 0.95, createSeededRandom(213));
+    const b = StatisticalUtils.bootstrapTCI(SLEEP_GROUP1, SLEEP_GROUP2, 999, 0.95, createSeededRandom(213));
+
+    expect(a.resamples).toBe(999);
+    expect(a.confidence_interval[0]).toBeLessThan(d);
+    expect(a.confidence_interval[1]).toBeGreaterThan(d);
+    expect(a.confidence_interval).toEqual(b.confidence_interval);
+  });
+});
+
+describe('powerExactCI', () => {
+  it("matches R's binom.test intervals", () => {
+    const [lower, upper] = StatisticalUtils.powerExactCI(50, 100);
+    expect(lower).toBeCloseTo(0.3983211, 6);
+    expect(upper).toBeCloseTo(0.6016789, 6);
+
+    const [lower8, upper8] = StatisticalUtils.powerExactCI(8, 10);
+    expect(lower8).toBeCloseTo(0.4439045, 6);
+    expect(upper8).toBeCloseTo(0.9747893, 6);
+  });
+
+  it('pins the bound at 0 or 1 when no or every simulation rejected', () => {
+    const [lower, upper] = StatisticalUtils.powerExactCI(0, 10);
+    expect(lower).toBe(0);
+    expect(upper).toBeCloseTo(0.3084971, 6);
+    expect(StatisticalUtils.powerExactCI(10, 10)[1]).toBe(1);
+  });
+});
+
+describe('correlated endpoints', () => {
+  it('factors a correlation matrix as L L^T', () => {
+    const matrix = [[1, 0.5, 0.2], [0.5, 1, 0.3], [0.2, 0.3, 1]];
+    const L = StatisticalUtils.choleskyDecomposition(matrix);
+    for (let i = 0; i < 3; i++) {
+      for (let j = 0; j < 3; j++) {
+        expect(L[i].reduce((sum, l, m) => sum + l * L[j][m], 0)).toBeCloseTo(matrix[i][j], 12);
+      }
+    }
+    expect(() => StatisticalUtils.choleskyDecomposition([[1, 1.2], [1.2, 1]])).toThrow();
+  });
+
+  it('draws vectors with the requested correlation', () => {
+    const L = StatisticalUtils.choleskyDecomposition([[1, 0.6], [0.6, 1]]);
+    const random = createSeededRandom(215);
+    const draws = Array.from({length: 20000}, () => StatisticalUtils.correlatedNormalVector(L, random));
+    const [x, y] = [draws.map(d => d[0]), draws.map(d => d[1])];
+    const [mx, my] = [mean(x), mean(y)];
+    const covariance = x.reduce((sum, v, i) => sum + (v - mx) * (y[i] - my), 0) / (x.length - 1);
+    expect(Math.abs(covariance / Math.sqrt(variance(x) * variance(y)) - 0.6)).toBeLessThan(0.03);
+  });
+});
+
+describe('convertStatistic', () => {
+  it('maps p = 0.05 to z = 1.96 and 4.32 bits', () => {
+    expect(StatisticalUtils.convertStatistic(0.05, 'p_value', 'z_score')).toBeCloseTo(1.959964, 5);
+    expect(StatisticalUtils.convertStatistic(0.05, 'p_value', 'z_score', false)).toBeCloseTo(1.644854, 5);
+    expect(StatisticalUtils.convertStatistic(0.05, 'p_value', 's_value')).toBeCloseTo(4.321928, 5);
+    expect(StatisticalUtils.convertStatistic(1.959964, 'z_score', 'p_value')).toBeCloseTo(0.05, 6);
+  });
+
+  it('round-trips through every kind', () => {
+    const s = StatisticalUtils.convertStatistic(0.003, 'p_value', 's_value');
+    const z = StatisticalUtils.convertStatistic(s, 's_value', 'z_score');
+    expect(StatisticalUtils.convertStatistic(z, 'z_score', 'p_value')).toBeCloseTo(0.003, 8);
+  });
+
+  it('rejects values outside each domain', () => {
+    expect(() => StatisticalUtils.convertStatistic(1.5, 'p_value', 'z_score')).toThrow();
+    expect(() => StatisticalUtils.convertStatistic(-1, 's_value', 'p_value')).toThrow();
+  });
+});
+
+describe('welchSatterthwaiteDF', () => {
+  it("matches the df of R's Welch test on the sleep data", () => {
+    const df = StatisticalUtils.welchSatterthwaiteDF(variance(SLEEP_GROUP1), 10, variance(SLEEP_GROUP2), 10);
+    expect(df).toBeCloseTo(17.776, 3);
+  });
+
+  it('falls to the smaller group df when one variance dominates', () => {
+    expect(StatisticalUtils.welchSatterthwaiteDF(1e6, 5, 1, 50)).toBeCloseTo(4, 3);
+  });
+});
+
+describe('effectSizeForSValue', () => {
+  it('integrates the expected p-value over the noncentral t', () => {
+    // E[p] = P(|T0| >= |T|) with T0 central and T noncentral t(38, 0.5 * sqrt(10)), by quadrature
+    expect(StatisticalUtils.expectedPValue(0.5, 20, 20)).toBeCloseTo(0.232165, 3);
+    expect(StatisticalUtils.expectedPValue(0, 20, 20)).toBeCloseTo(0.5, 3);
+  });
+
+  it('solves for the effect whose expected p-value carries the target bits', () => {
+    const d = StatisticalUtils.effectSizeForSValue(4, 20, 20);
+    expect(StatisticalUtils.expectedPValue(d, 20, 20)).toBeCloseTo(1 / 16, 5);
+    expect(StatisticalUtils.effectSizeForSValue(5, 20, 20)).toBeGreaterThan(d);
+    // One bit or less is met with no effect at all
+    expect(StatisticalUtils.effectSizeForSValue(0.5, 20, 20)).toBe(0);
+  });
+});
+
+describe('summarizeValues', () => {
+  it('reports the fixed percentile set of unsorted values', () => {
+    const values = Array.from({length: 101}, (_, i) => (i * 37) % 101);
+    expect(StatisticalUtils.summarizeValues(values)).toEqual({ min: 0, p5: 5, p25: 25, p50: 50, p75: 75, p95: 95, max: 100 });
+  });
+});
+
+describe('posteriorEffectSize', () => {
+  it('shrinks the observed d toward the prior by precision', () => {
+    const posterior = StatisticalUtils.posteriorEffectSize(SLEEP_GROUP1, SLEEP_GROUP2, 0, 1);
+    expect(posterior.observed_effect_size).toBeCloseTo(-0.832181, 5);
+    expect(posterior.observed_se).toBeCloseTo(0.466169, 5);
+    expect(posterior.posterior_mean).toBeCloseTo(-0.683621, 5);
+    expect(posterior.posterior_sd).toBeCloseTo(0.422515, 5);
+    expect(posterior.credible_interval[0]).toBeCloseTo(-1.511735, 5);
+    expect(posterior.credible_interval[1]).toBeCloseTo(0.144492, 5);
+  });
+
+  it('approaches the data as the prior widens', () => {
+    const posterior = StatisticalUtils.posteriorEffectSize(SLEEP_GROUP1, SLEEP_GROUP2, 0, 1000);
+    expect(posterior.posterior_mean).toBeCloseTo(posterior.observed_effect_size, 5);
+  });
+});
+
+describe('mannWhitneyU', () => {
+  it("matches R's wilcox.test(extra ~ group, data = sleep, exact = FALSE)", () => {
+    // W = 25.5, p-value = 0.06933 (normal approximation with tie and continuity correction)
+    const result = StatisticalUtils.mannWhitneyU(SLEEP_GROUP1, SLEEP_GROUP2);
+    expect(result.u_statistic).toBe(25.5);
+    expect(result.p_value).toBeCloseTo(0.06933, 5);
+    expect(result.rank_biserial).toBeCloseTo(-0.49, 12);
+  });
+});
+
+describe('compareTests', () => {
+  it('reports where the t-test and Mann-Whitney decisions split', () => {
+    // t-test p = 0.07919, Mann-Whitney p = 0.06933
+    const split = StatisticalUtils.compareTests(SLEEP_GROUP1, SLEEP_GROUP2, 0.075);
+    expect(split.t_test.significant).toBe(false);
+    expect(split.mann_whitney.significant).toBe(true);
+    expect(split.decisions_agree).toBe(false);
+
+    const agree = StatisticalUtils.compareTests(SLEEP_GROUP1, SLEEP_GROUP2, 0.1);
+    expect(agree.decisions_agree).toBe(true);
+  });
+});
+
+describe('variance_denominator', () => {
+  it("shrinks the pooled SD by sqrt((N - 2) / N) under the biased 'n' denominator", () => {
+    const unbiased = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2);
+    const biased = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2, { variance_denominator: 'n' });
+    const inflation = Math.sqrt(20 / 18);
+    expect(biased.t_statistic).toBeCloseTo(unbiased.t_statistic * inflation, 10);
+    expect(biased.effect_size).toBeCloseTo(unbiased.effect_size * inflation, 10);
+    expect(biased.df).toBe(18);
+    expect(biased.p_value).toBeLessThan(unbiased.p_value);
+  });
+});
+
+describe('designEffect', () => {
+  it('is 1 + (m - 1) * icc', () => {
+    expect(StatisticalUtils.designEffect(5, 0.2)).toBeCloseTo(1.8, 12);
+    expect(StatisticalUtils.designEffect(1, 0.5)).toBe(1);
+    expect(StatisticalUtils.designEffect(10, 0)).toBe(1);
+  });
+});
+
+describe('simulationsForPowerPrecision', () => {
+  it('is ceil(z^2 p (1 - p) / h^2)', () => {
+    // 1.959964^2 * 0.16 / 0.01^2 = 6146.33
+    expect(StatisticalUtils.simulationsForPowerPrecision(0.8, 0.01)).toBe(6147);
+    // Worst case p = 0.5: 1.959964^2 * 0.25 / 0.02^2 = 2400.91
+    expect(StatisticalUtils.simulationsForPowerPrecision(0.5, 0.02)).toBe(2401);
+  });
+
+  it('never asks for fewer than one simulation', () => {
+    expect(StatisticalUtils.simulationsForPowerPrecision(1, 0.01)).toBe(1);
+  });
+});
+
+describe('ciOverlap', () => {
+  it('measures the shared stretch of two intervals', () => {
+    const result = StatisticalUtils.ciOverlap([0, 2], [1.5, 3]);
+    expect(result.overlap).toBe(true);
+    expect(result.overlap_amount).toBeCloseTo(0.5, 12);
+  });
+
+  it('counts touching intervals as overlapping and separated ones as not', () => {
+    expect(StatisticalUtils.ciOverlap([0, 1], [1, 2])).toEqual({ overlap: true, overlap_amount: 0 });
+    expect(StatisticalUtils.ciOverlap([0, 1], [1.5, 2])).toEqual({ overlap: false, overlap_amount: 0 });
+  });
+
+  it('rejects reversed or infinite intervals', () => {
+    expect(() => StatisticalUtils.ciOverlap([1, 0], [0, 1])).toThrow();
+    expect(() => StatisticalUtils.ciOverlap([0, Infinity], [0, 1])).toThrow();
+  });
+});
+
+describe('clampPValue', () => {
+  it('pulls rounded-out p-values back into [0, 1] and passes NaN through', () => {
+    expect(StatisticalUtils.clampPValue(1 + 1e-15)).toBe(1);
+    expect(StatisticalUtils.clampPValue(-1e-17)).toBe(0);
+    expect(StatisticalUtils.clampPValue(0.3)).toBe(0.3);
+    expect(StatisticalUtils.clampPValue(NaN)).toBeNaN();
+  });
+});
+
+describe('calculateSValue', () => {
+  it('is -log2(p), never negative near p = 1', () => {
+    expect(StatisticalUtils.calculateSValue(0.05)).toBeCloseTo(4.321928, 6);
+    expect(StatisticalUtils.calculateSValue(1 + 1e-15)).toBe(0);
+    expect(StatisticalUtils.calculateSValue(0)).toBe(Infinity);
+  });
+});
+
+describe('varianceReductionFactor', () => {
+  it('compares the binomial variance with the spread of batch power estimates', () => {
+    // 20 batches of 2 with rates alternating 1, 0: p = 0.5, batch variance 0.25 * 20 / 19,
+    // per-simulation variance twice that, so the factor is 0.25 / (0.5 * 20 / 19) = 0.475
+    const outcomes = Array.from({length: 40}, (_, i) => Math.floor(i / 2) % 2 === 0);
+    expect(StatisticalUtils.varianceReductionFactor(outcomes)).toBeCloseTo(0.475, 12);
+  });
+
+  it('is null when the batch estimates do not vary or there are too few outcomes', () => {
+    const alternating = Array.from({length: 40}, (_, i) => i % 2 === 0);
+    expect(StatisticalUtils.varianceReductionFactor(alternating)).toBeNull();
+    expect(StatisticalUtils.varianceReductionFactor([true, false])).toBeNull();
+  });
+});
+
+describe('storeyQValues', () => {
+  it('scales the BH step-up by pi0 and keeps the input order', () => {
+    // pi0 = #{p > 0.5} / (5 * 0.5) = 0.8; sorted BH values 0.05, 0.05, 0.05, 0.75, 0.8
+    const { q_values, pi0 } = StatisticalUtils.storeyQValues([0.6, 0.01, 0.8, 0.03, 0.02]);
+    expect(pi0).toBeCloseTo(0.8, 12);
+    [0.6, 0.04, 0.64, 0.04, 0.04].forEach((q, i) => expect(q_values[i]).toBeCloseTo(q, 12));
+  });
+
+  it('caps pi0 at 1', () => {
+    expect(StatisticalUtils.storeyQValues([0.9, 0.95, 0.7]).pi0).toBe(1);
+  });
+});
+
+describe('combinePValues', () => {
+  const p_values = [0.01, 0.2, 0.3];
+
+  it("gives Fisher's chi-square tail", () => {
+    // -2 sum(ln p) = 14.8372 on 6 df
+    expect(StatisticalUtils.combinePValues(p_values, 'fisher')).toBeCloseTo(0.0215618, 6);
+  });
+
+  it("gives Stouffer's upper-tail p", () => {
+    // sum(z) / sqrt(3) = 2.13179
+    expect(StatisticalUtils.combinePValues(p_values, 'stouffer')).toBeCloseTo(0.0165120, 6);
+  });
+
+  it('gives the harmonic mean p-value', () => {
+    // 3 / (100 + 5 + 3.333)
+    expect(StatisticalUtils.combinePValues(p_values, 'harmonic_mean')).toBeCloseTo(0.0276923, 7);
+  });
+
+  it('rejects empty input and p-values outside (0, 1]', () => {
+    expect(() => StatisticalUtils.combinePValues([], 'fisher')).toThrow();
+    expect(() => StatisticalUtils.combinePValues([0, 0.5], 'stouffer')).toThrow();
+  });
+});
+
+describe('classifyEffectSize', () => {
+  it("bands |d| at Cohen's cut-offs, each cut-off opening the next band", () => {
+    expect(StatisticalUtils.classifyEffectSize(0.1)).toBe('negligible');
+    expect(StatisticalUtils.classifyEffectSize(-0.2)).toBe('small');
+    expect(StatisticalUtils.classifyEffectSize(0.5)).toBe('medium');
+    expect(StatisticalUtils.classifyEffectSize(-1.2)).toBe('large');
+  });
+
+  it('uses custom bands', () => {
+    expect(StatisticalUtils.classifyEffectSize(0.5, [0.1, 0.3, 0.6])).toBe('medium');
+    expect(StatisticalUtils.classifyEffectSize(0.6, [0.1, 0.3, 0.6])).toBe('large');
+  });
+});
+
+describe('min_df', () => {
+  it('floors the df and takes the p-value from the floored t distribution', () => {
+    const exact = StatisticalUtils.twoSampleTTest([1, 2], [0, 0.5]);
+    const floored = StatisticalUtils.twoSampleTTest([1, 2], [0, 0.5], { min_df: 10 });
+    expect(exact.df).toBe(2);
+    expect(floored.df).toBe(10);
+    expect(floored.t_statistic).toBe(exact.t_statistic);
+    expect(floored.p_value).toBeCloseTo(StatisticalUtils.studentTPValue(exact.t_statistic, 10), 12);
+    expect(floored.p_value).toBeLessThan(exact.p_value);
+  });
+
+  it('leaves df above the floor alone', () => {
+    const result = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2, { min_df: 5 });
+    expect(result.df).toBe(18);
+  });
+});
+
+describe('pairedTTest', () => {
+  it("matches R's t.test(sleep$extra[1:10], sleep$extra[11:20], paired = TRUE)", () => {
+    // t = -4.0621, df = 9, p-value = 0.002833
+    const result = StatisticalUtils.pairedTTest(SLEEP_GROUP1, SLEEP_GROUP2);
+    expect(result.t_statistic).toBeCloseTo(-4.0621, 4);
+    expect(result.df).toBe(9);
+    expect(result.p_value).toBeCloseTo(0.002833, 6);
+    // d_z = mean difference / SD of the differences = t / sqrt(n)
+    expect(result.effect_size).toBeCloseTo(-1.284558, 6);
+  });
+
+  it('requires groups of equal length', () => {
+    expect(() => StatisticalUtils.pairedTTest([1, 2, 3], [1, 2])).toThrow('equal length');
+  });
+});
+
+describe('one-sided alternatives', () => {
+  it("match R's t.test(..., var.equal = TRUE, alternative = 'less')", () => {
+    // p-value = 0.03959, half the two-sided 0.07919 since t < 0
+    const less = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2, { alternative: 'less' });
+    const greater = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2, { alternative: 'greater' });
+    expect(less.p_value).toBeCloseTo(0.039593, 6);
+    expect(greater.p_value).toBeCloseTo(1 - 0.039593, 6);
+  });
+
+  it("match R's wilcox.test(..., exact = FALSE, alternative = 'less')", () => {
+    // The continuity correction moves toward the null in each direction: 0.03466 and 0.97075
+    expect(StatisticalUtils.mannWhitneyU(SLEEP_GROUP1, SLEEP_GROUP2, 'less').p_value).toBeCloseTo(0.034664, 6);
+    expect(StatisticalUtils.mannWhitneyU(SLEEP_GROUP1, SLEEP_GROUP2, 'greater').p_value).toBeCloseTo(0.970752, 6);
+  });
+});
+
+describe('non-normal populations', () => {
+  const skewness = (values: number[]) => {
+    const m = mean(values);
+    return mean(values.map(x => (x - m) ** 3)) / variance(values) ** 1.5;
+  };
+
+  const cases: [string, Distribution, number][] = [
+    // Skewness: (e^(sigma^2) + 2) sqrt(e^(sigma^2) - 1) for the lognormal, 2 for the exponential
+    ['lognormal', { type: 'lognormal', sigma: 0.5 }, 1.750],
+    ['exponential', { type: 'exponential' }, 2],
+    ['uniform', { type: 'uniform' }, 0],
+    ['student_t', { type: 'student_t', df: 5 }, 0]
+  ];
+
+  it.each(cases)('standardizes the %s shape to the group mean and variance', (_, distribution, expected_skewness) => {
+    const samples = StatisticalUtils.generateSamples(200000, 5, 2, distribution, createSeededRandom(258));
+    expect(Math.abs(mean(samples) - 5)).toBeLessThan(0.02);
+    expect(Math.abs(variance(samples) - 4)).toBeLessThan(0.12);
+    expect(Math.abs(skewness(samples) - expected_skewness)).toBeLessThan(0.2);
+  });
+
+  it('keeps the uniform within mean +- sqrt(3) std', () => {
+    const samples = StatisticalUtils.generateSamples(10000, 5, 2, { type: 'uniform' }, createSeededRandom(258));
+    expect(Math.min(...samples)).toBeGreaterThanOrEqual(5 - 2 * Math.sqrt(3));
+    expect(Math.max(...samples)).toBeLessThan(5 + 2 * Math.sqrt(3));
+  });
+});
+
+describe('createPValueHistogram', () => {
+  it('bins p-values into equal-width bins, the last one closed at 1', () => {
+    const histogram = StatisticalUtils.createPValueHistogram([0, 0.05, 0.1, 0.55, 0.99, 1], 0.1, 10);
+    expect(histogram).toHaveLength(10);
+    expect(histogram.map(b => b.count)).toEqual([2, 1, 0, 0, 0, 1, 0, 0, 0, 2]);
+    expect(histogram[0]).toMatchObject({ bin_start: 0, bin_end: 0.1, significant: true });
+    expect(histogram[1].significant).toBe(false);
+  });
+});
+
+describe('alpha-aligned histogram', () => {
+  it('adds an edge at alpha so the significant bins end exactly there', () => {
+    const histogram = StatisticalUtils.createPValueHistogram([0.01, 0.04, 0.06], 0.03, 10, true);
+    expect(histogram).toHaveLength(11);
+    expect(histogram[0]).toMatchObject({ bin_start: 0, bin_end: 0.03, count: 1, significant: true });
+    expect(histogram[1]).toMatchObject({ bin_start: 0.03, bin_end: 0.1, count: 2, significant: false });
+  });
+
+  it('leaves the bins alone when alpha already is an edge', () => {
+    expect(StatisticalUtils.createPValueHistogram([0.01], 0.05, 20, true)).toHaveLength(20);
+  });
+});
+
+describe("Hedges' g", () => {
+  it('scales d by 1 - 3 / (4 df - 1)', () => {
+    // d = -0.832181 on the sleep data with df = 18
+    const result = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2);
+    expect(result.hedges_g).toBeCloseTo(result.effect_size * (1 - 3 / 71), 12);
+    expect(result.hedges_g).toBeCloseTo(-0.797018, 5);
+  });
+
+  it('uses the paired df for d_z', () => {
+    const result = StatisticalUtils.pairedTTest(SLEEP_GROUP1, SLEEP_GROUP2);
+    expect(result.hedges_g).toBeCloseTo(result.effect_size * (1 - 3 / 35), 12);
+  });
+});
+
+describe('bootstrapPercentileCI', () => {
+  it('takes the tail percentiles of the resampled d values', () => {
+    const replay = createSeededRandom(261);
+    const d_stars = Array.from({length: 500}, () => StatisticalUtils.cohensD(
+      StatisticalUtils.resample(SLEEP_GROUP1, replay),
+      StatisticalUtils.resample(SLEEP_GROUP2, replay)
+    )).sort((a, b) => a - b);
+
+    const result = StatisticalUtils.bootstrapPercentileCI(SLEEP_GROUP1, SLEEP_GROUP2, 500, 0.9, createSeededRandom(261));
+    expect(result.resamples).toBe(500);
+    const tail = (1 - 0.9) / 2;
+    expect(result.confidence_interval).toEqual([
+      StatisticalUtils.percentile(d_stars, tail),
+      StatisticalUtils.percentile(d_stars, 1 - tail)
+    ]);
+  });
+});
+
+describe('jackknifeQuantileSE', () => {
+  it('approximates the standard error of a sample quantile', () => {
+    // Median of n uniforms: SE = 1 / (2 f sqrt(n)) with density f = 1, 0.0079 at n = 4000
+    const random = createSeededRandom(262);
+    const replicates = Array.from({length: 4000}, () => random());
+    const se = StatisticalUtils.jackknifeQuantileSE(replicates, 0.5);
+    expect(se).toBeGreaterThan(0.0079 / 2);
+    expect(se).toBeLessThan(0.0079 * 2);
+  });
+
+  it('is undefined with fewer than two replicates', () => {
+    expect(StatisticalUtils.jackknifeQuantileSE([0.3], 0.5)).toBeNaN();
+  });
+});
+
+describe('powerWilsonCI', () => {
+  it("matches R's prop.test without continuity correction", () => {
+    const [lower, upper] = StatisticalUtils.powerWilsonCI(8, 10);
+    expect(lower).toBeCloseTo(0.4901625, 6);
+    expect(upper).toBeCloseTo(0.9433178, 6);
+  });
+
+  it('keeps a nonzero width at the edges', () => {
+    const [lower0, upper0] = StatisticalUtils.powerWilsonCI(0, 20);
+    expect(lower0).toBeCloseTo(0, 10);
+    expect(upper0).toBeCloseTo(0.1611252, 6);
+    const [lower20, upper20] = StatisticalUtils.powerWilsonCI(20, 20);
+    expect(lower20).toBeCloseTo(0.8388748, 6);
+    expect(upper20).toBe(1);
+  });
+
+  it('rejects counts outside 0..total', () => {
+    expect(() => StatisticalUtils.powerWilsonCI(11, 10)).toThrow('between 0 and the total');
+    expect(() => StatisticalUtils.powerWilsonCI(0, 0)).toThrow('positive integer');
+  });
+});
+
+describe('adjustedSignificantCount', () => {
+  const p_values = [0.001, 0.008, 0.039, 0.041, 0.042, 0.06, 0.074, 0.205, 0.212, 0.216];
+
+  it('counts raw, Bonferroni and Benjamini-Hochberg rejections', () => {
+    // R: sum(p < 0.05); sum(p.adjust(p, 'bonferroni') < 0.05); sum(p.adjust(p, 'BH') < 0.05)
+    expect(StatisticalUtils.adjustedSignificantCount(p_values, 0.05, 'none')).toBe(5);
+    expect(StatisticalUtils.adjustedSignificantCount(p_values, 0.05, 'bonferroni')).toBe(1);
+    expect(StatisticalUtils.adjustedSignificantCount(p_values, 0.05, 'benjamini_hochberg')).toBe(2);
+  });
+
+  it('lets BH reject everything when every p-value is small', () => {
+    expect(StatisticalUtils.adjustedSignificantCount([0.01, 0.02, 0.03, 0.04], 0.05, 'benjamini_hochberg')).toBe(4);
+    expect(StatisticalUtils.adjustedSignificantCount([0.01, 0.02, 0.03, 0.04], 0.05, 'bonferroni')).toBe(1);
+  });
+});
+
+describe('commonLanguageEffectSize', () => {
+  it('maps each effect scale to a probability of superiority', () => {
+    expect(StatisticalUtils.commonLanguageEffectSize(0)).toBe(0.5);
+    // pnorm(0.5 / sqrt(2)); pnorm(0.5)
+    expect(StatisticalUtils.commonLanguageEffectSize(0.5)).toBeCloseTo(0.638163, 6);
+    expect(StatisticalUtils.commonLanguageEffectSize(0.5, 'paired')).toBeCloseTo(0.691462, 6);
+    expect(StatisticalUtils.commonLanguageEffectSize(0.2, 'mann_whitney')).toBeCloseTo(0.6, 12);
+  });
+
+  it('maps an interval centred at zero to one centred at 0.5', () => {
+    const lower = StatisticalUtils.commonLanguageEffectSize(-0.4);
+    const upper = StatisticalUtils.commonLanguageEffectSize(0.4);
+    expect((lower + upper) / 2).toBeCloseTo(0.5, 12);
+  });
+});
+
+describe('criticalEffectSize', () => {
+  it('is the t critical value on the effect-size scale', () => {
+    // qt(0.975, 38) * sqrt(1/20 + 1/20); qt(0.95, 38) * sqrt(1/20 + 1/20)
+    expect(StatisticalUtils.criticalEffectSize(20, 20, 0.05)).toBeCloseTo(0.640170, 5);
+    expect(StatisticalUtils.criticalEffectSize(20, 20, 0.05, false)).toBeCloseTo(0.533146, 5);
+    expect(StatisticalUtils.criticalEffectSize(20, 20, 0.1)).toBeCloseTo(
+      StatisticalUtils.criticalEffectSize(20, 20, 0.05, false), 12);
+  });
+
+  it('drops as alpha grows and as the groups grow', () => {
+    const strict = StatisticalUtils.criticalEffectSize(20, 20, 0.01);
+    const lenient = StatisticalUtils.criticalEffectSize(20, 20, 0.1);
+    expect(lenient).toBeLessThan(strict);
+    expect(StatisticalUtils.criticalEffectSize(80, 80, 0.01)).toBeLessThan(strict);
+  });
+
+  it('rejects tiny groups and alphas outside (0, 1)', () => {
+    expect(() => StatisticalUtils.criticalEffectSize(1, 20, 0.05)).toThrow('at least 2');
+    expect(() => StatisticalUtils.criticalEffectSize(20, 20, 0)).toThrow('Alpha');
+  });
+});
+
+describe('jzsBayesFactor', () => {
+  it('matches the BayesFactor package on the sleep data', () => {
+    // R: ttestBF(SLEEP_GROUP1, SLEEP_GROUP2) -> 1.265925 (r = sqrt(2) / 2)
+    const t = StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2).t_statistic;
+    expect(StatisticalUtils.jzsBayesFactor(t, 10, 10)).toBeCloseTo(1.265925, 4);
+  });
+
+  it('matches direct integration of the Rouder et al. integral', () => {
+    // mpmath quad over g in (0, inf)
+    expect(StatisticalUtils.jzsBayesFactor(0, 20, 20)).toBeCloseTo(0.308794, 5);
+    expect(StatisticalUtils.jzsBayesFactor(2, 20, 20)).toBeCloseTo(1.453931, 5);
+    expect(StatisticalUtils.jzsBayesFactor(5, 20, 20) / 1252.517199).toBeCloseTo(1, 5);
+    expect(StatisticalUtils.jzsBayesFactor(3, 50, 50)).toBeCloseTo(10.383607, 4);
+  });
+
+  it('grows with |t| and is symmetric in its sign', () => {
+    const factors = [0, 0.5, 1, 2, 3, 5, 8].map(t => StatisticalUtils.jzsBayesFactor(t, 20, 20));
+    factors.slice(1).forEach((bf, k) => expect(bf).toBeGreaterThan(factors[k]));
+    expect(StatisticalUtils.jzsBayesFactor(-3, 20, 20)).toBeCloseTo(StatisticalUtils.jzsBayesFactor(3, 20, 20), 10);
+  });
+
+  it('stays finite for extreme t', () => {
+    const bf = StatisticalUtils.jzsBayesFactor(40, 20, 20);
+    expect(Number.isFinite(bf)).toBe(true);
+    expect(bf).toBeGreaterThan(1e20);
+  });
+});
+
+describe('glassDelta', () => {
+  it("scales the mean difference by the control group's SD only", () => {
+    // (mean1 - mean2) / sd(group2) and / sd(group1); Cohen's d pools both SDs
+    const glass = StatisticalUtils.glassDelta(SLEEP_GROUP1, SLEEP_GROUP2);
+    expect(glass.effect_size).toBeCloseTo(-0.789113, 5);
+    expect(StatisticalUtils.glassDelta(SLEEP_GROUP1, SLEEP_GROUP2, 1).effect_size).toBeCloseTo(-0.883170, 5);
+    expect(StatisticalUtils.twoSampleTTest(SLEEP_GROUP1, SLEEP_GROUP2).effect_size).toBeCloseTo(-0.832181, 5);
+  });
+
+  it('uses the large-sample variance for its interval', () => {
+    // delta -/+ qnorm(0.975) * sqrt(20 / 100 + delta^2 / 18)
+    const [lower, upper] = StatisticalUtils.glassDelta(SLEEP_GROUP1, SLEEP_GROUP2).confidence_interval;
+    expect(lower).toBeCloseTo(-1.738420, 5);
+    expect(upper).toBeCloseTo(0.160195, 5);
+  });
+});
+
+describe('noncentralTCI', () => {
+  it('matches direct inversion of the noncentral t', () => {
+    // mpmath: noncentral t CDF by quadrature over the chi-square, solved for the ncp
+    const [lower, upper] = StatisticalUtils.noncentralTCI(0.5, 20, 20);
+    expect(lower).toBeCloseTo(-0.133018, 4);
+    expect(upper).toBeCloseTo(1.126661, 4);
+
+    const [lower8, upper8] = StatisticalUtils.noncentralTCI(1.2, 8, 8);
+    expect(lower8).toBeCloseTo(0.107951, 4);
+    expect(upper8).toBeCloseTo(2.256676, 4);
+
+    const [lower90, upper90] = StatisticalUtils.noncentralTCI(0.5, 10, 15, 0.9);
+    expect(lower90).toBeCloseTo(-0.187555, 4);
+    expect(upper90).toBeCloseTo(1.176945, 4);
+  });
+
+  it('agrees with the analytic interval for large samples', () => {
+    const [lower, upper] = StatisticalUtils.noncentralTCI(0.3, 500, 500);
+    const [analytic_lower, analytic_upper] = StatisticalUtils.calculateConfidenceInterval(0.3, 500, 500);
+    expect(lower).toBeCloseTo(analytic_lower, 2);
+    expect(upper).toBeCloseTo(analytic_upper, 2);
+  });
+});
+
+describe('contaminateSamples', () => {
+  const kurtosis = (values: number[]) => {
+    const m = mean(values);
+    return mean(values.map(x => (x - m) ** 4)) / variance(values) ** 2;
+  };
+
+  it('gives the contaminated normal its heavier tails', () => {
+    const random = createSeededRandom(282);
+    const clean = StatisticalUtils.generateSamples(200000, 0, 1, { type: 'normal' }, random);
+    const contaminated = StatisticalUtils.contaminateSamples(clean, 0, { fraction: 0.1, scale: 3 }, random);
+
+    // 0.9 N(0, 1) + 0.1 N(0, 9): variance 1.8, kurtosis 3 (0.9 + 0.1 * 81) / 1.8^2 = 8.33
+    expect(Math.abs(kurtosis(clean) - 3)).toBeLessThan(0.1);
+    expect(Math.abs(variance(contaminated) - 1.8)).toBeLessThan(0.05);
+    expect(Math.abs(kurtosis(contaminated) - 25 / 3)).toBeLessThan(0.8);
+  });
+
+  it('leaves the sample alone at a zero fraction', () => {
+    const samples = [1, 2, 3];
+    expect(StatisticalUtils.contaminateSamples(samples, 2, { fraction: 0, scale: 5 })).toBe(samples);
+    expect(StatisticalUtils.contaminateSamples(samples, 2, { fraction: 1, scale: 5 })).toEqual([-3, 2, 7]);
+  });
+
+  it('rejects fractions outside [0, 1] and non-positive scales', () => {
+    expect(() => StatisticalUtils.validateContamination({ fraction: 1.5, scale: 3 }, 1)).toThrow('fraction');
+    expect(() => StatisticalUtils.validateContamination({ fraction: 0.1, scale: 0 }, 2)).toThrow('scale');
+  });
+});
@@ -8,9 +8,12 @@ import {
   SimulationSession
 } from '../types/simulation.types';
 import { databaseService } from '../services/database.service';
-import { multiPairSimulationEngine } from '../services/multi-pair-simulation';
+import { SimulationError, multiPairSimulationEngine } from '../services/multi-pair-simulation';
 import { useChartsStore } from './charts.store';
 
+// Abort handle of the running simulation, so cancelSimulation can stop the engine
+let simulationAbort: AbortController | null = null;
+
 interface SimulationState {
   // Current session state
   currentSession: SimulationSession | null;
@@ -217,6 +220,9 @@ export const useSimulationStore = create<SimulationStore>()(
         // Simulation management
         runSimulation: async () => {
           set({ isLoading: true, error: null });
+          simulationAbort?.abort();
+          const abort = new AbortController();
+          simulationAbort = abort;
 
           try {
             const { currentSession } = get();
@@ -231,7 +237,8 @@ export const useSimulationStore = create<SimulationStore>()(
               currentSession.parameters,
               (progress) => {
                 console.log('Simulation progress:', progress);
-              }
+              },
+              abort.signal
             );
 
             console.log('Multi-pair simulation completed successfully');
@@ -259,6 +266,10 @@ export const useSimulationStore = create<SimulationStore>()(
             console.log('Session updated with simulation results');
             console.log('Chart store updated with new simulation results');
           } catch (error) {
+            if (error instanceof SimulationError && error.kind === 'cancelled') {
+              console.log('Simulation cancelled');
+              return;
+            }
             console.error('Simulation failed:', error);
             set({
               isLoading: false,
@@ -268,6 +279,8 @@ export const useSimulationStore = create<SimulationStore>()(
         },
 
         cancelSimulation: () => {
+          simulationAbort?.abort();
+          simulationAbort = null;
           set({ isLoading: false });
         },
 
@@ -7,6 +7,7 @@ export interface SamplePair {
   group1: PopulationParams;
   group2: PopulationParams;
   sample_size_per_group: number;
+  group2_sample_size?: number; // Unbalanced designs: overrides sample_size_per_group for group 2
   enabled: boolean;
   color_scheme?: string;
 }
@@ -28,7 +29,7 @@ export interface GlobalSimulationSettings {
   significance_levels: number[];
   confidence_level: number;
   random_seed?: number;
-  test_type: 'welch' | 'pooled' | 'mann_whitney';
+  test_type: TestType;
 }
 
 export interface UIPreferences {
@@ -150,14 +151,155 @@ export interface SimulationParams {
   num_simulations: number;
   hypothesized_effect_size: number;
   alpha_level: number;
+  seed?: number; // Seeds the sampling RNG; omit for non-deterministic runs
+  verify_reproducibility?: boolean; // With a seed, run twice and fail unless results match exactly
+  diagnostics?: boolean; // Collect extra teaching/diagnostic outputs (slower, larger results)
+  significance_rule?: SignificanceRule; // Defaults to p < alpha
+  group1_distribution?: Distribution; // Defaults to normal(group1_mean, group1_std)
+  group2_distribution?: Distribution; // Defaults to normal(group2_mean, group2_std)
+  group1_contamination?: Contamination; // Occasional wide draws (outliers) mixed into group 1
+  group2_contamination?: Contamination; // Occasional wide draws (outliers) mixed into group 2
+  store_individual_results?: boolean; // Default true; false keeps memory flat: no individual_results, t-digest percentiles and data-ranged histograms, no BH count
+  estimand?: Estimand; // What mean_effect_size estimates; defaults to 'sample_distribution'
+  effect_size_metric?: EffectSizeMetric; // Scale of mean_effect_size / effect_size_ci; defaults to 'cohens_d'
+  glass_control_group?: 1 | 2; // Group whose SD scales Glass's delta (default 2)
+  multiple_comparison?: MultipleComparison; // Family correction for adjusted_significant_count (default 'none')
+  ci_method?: CiMethod; // How each simulation's effect-size CI is built; defaults to analytic
+  // Level of each simulation's effect-size CI (default 0.95; 1 - alpha_level matches a CI-based rule to alpha)
+  ci_confidence_level?: number;
+  num_endpoints?: number; // > 1 adds a correlated multi-endpoint analysis (normal outcomes)
+  endpoint_correlation?: number; // Exchangeable correlation between endpoints, default 0
+  buffered_sampling?: boolean; // Bulk inverse-CDF normal draws; faster, different random stream
+  group2_sample_size?: number; // Overrides sample_size_per_group for group 2 (unbalanced designs)
+  debug_rng?: boolean; // Record the first uniform draws in metadata.rng_draws
+  debug_rng_draws?: number; // How many draws to record (default 16, capped at 64)
+  percentile_summary?: boolean; // Return percentile_summary instead of individual_results
+  return_sample_datasets?: number; // Attach this many raw (group1, group2) datasets (max 20)
+  scatter_sample_size?: number; // Cap on (effect_size, p_value) points in scatter_sample, e.g. 5000
+  power_trace?: boolean; // Record the running power at log-spaced simulation counts
+  bayes_factor?: boolean; // Attach each simulation's JZS Bayes factor (independent-groups t modes)
+  noncentral_ci_threshold?: number; // |d| above which the CI switches to the exact noncentral-t interval
+  variance_denominator?: VarianceDenominator; // Defaults to 'n_minus_1'
+  test_type?: TestType; // Defaults to 'pooled'; 'mann_whitney' reports rank-biserial, 'paired' d_z effect sizes
+  num_permutations?: number; // Label shuffles per simulation for the 'permutation' test (default 1000)
+  min_df?: number; // Clamp t-test df upward; unset keeps the exact df
+  alternative?: Alternative; // One-sided tests for directional hypotheses (default 'two_sided')
+  cluster_size?: number; // Subjects per cluster (default 1 = no clustering)
+  icc?: number; // Intraclass correlation within clusters, in [0, 1)
+  effect_prior?: EffectPrior; // Draw the true standardized effect per simulation (assurance)
+  pvalue_strata?: number[]; // Stratum edges for effect_by_pvalue_stratum (default [0, 0.01, 0.05, 1])
+  rope?: [number, number]; // Region of practical equivalence on the standardized effect scale
+  compare_tests?: boolean; // Also run Mann-Whitney on each dataset and summarize disagreement with the t-test
+  antithetic_sampling?: boolean; // Pair each simulation with its mirrored draws (one-sided tests; not for mixtures, clusters or mixed designs)
+  storey_lambda?: number; // Tuning point for the Storey pi0 estimate (default 0.5)
+  self_calibrate?: boolean; // Estimate the test's actual size from a preliminary null run
+  calibrate_alpha?: boolean; // With self_calibrate: test at the alpha whose estimated size is alpha_level
+  include_null_calibration?: boolean; // Also run the design under the null and report null_rejection_rate
+  effect_size_thresholds?: [number, number, number]; // |d| cut-offs for small/medium/large (default Cohen's 0.2/0.5/0.8; Mann-Whitney: |r|, no default)
+  parallel_workers?: number; // Web Workers sharing the simulation loop, or runPowerGrid's cells (default 1: serial)
+  per_simulation_timeout_ms?: number; // Time budget for resampling; cuts n_boot and num_permutations short when exceeded
+  null_fraction?: number; // Mixed mode: probability each simulation runs under the null instead
+  histogram_bins?: number; // Bins across [0, 1] for the p-value histograms (default 20)
+  histogram_align_to_alpha?: boolean; // Add a bin edge at alpha_level (bins beside it become unequal)
+}
+
+// Prior on the true standardized effect; group 1's mean is set from group 2's plus d * pooled SD
+export type EffectPrior =
+  | { type: 'point'; value: number }
+  | { type: 'normal'; mean: number; sd: number };
+
+// 'n' uses the biased population variance throughout the t-test (teaching demonstrations)
+export type VarianceDenominator = 'n_minus_1' | 'n';
+
+// Direction of the alternative hypothesis; 'greater' means group 1 exceeds group 2
+export type Alternative = 'two_sided' | 'greater' | 'less';
+
+// Equal-variance (Student) vs unequal-variance (Welch) t-test
+export type VarianceAssumption = 'pooled' | 'welch';
+
+export interface TTestOptions {
+  variance_denominator?: VarianceDenominator;
+  variance_assumption?: VarianceAssumption; // Defaults to 'pooled'
+  min_df?: number; // Floor on the t distribution's df (teaching scenarios with tiny samples)
+  alternative?: Alternative; // Defaults to 'two_sided'
+  confidence_level?: number; // Effect-size CI level, default 0.95
+}
+
+export type CiMethod =
+  | { type: 'analytic' }
+  | { type: 'bootstrap_t'; n_boot: number } // Studentized bootstrap
+  | { type: 'bootstrap_percentile'; n_boot?: number } // Percentile bootstrap, n_boot default 2000
+  | { type: 'noncentral_t' }; // Exact interval from inverting the noncentral t (independent groups)
+
+// 'population_effect': mean_effect_size is the bias-corrected (Hedges' g) estimate of the true d
+// 'sample_distribution': mean_effect_size is the plain average of the simulated Cohen's d values
+export type Estimand = 'population_effect' | 'sample_distribution';
+
+// Which per-simulation effect size the mean and percentile aggregates are computed on.
+// 'glass_delta' also replaces SimulationResult.effect_size (and its CI) with Glass's delta
+export type EffectSizeMetric = 'cohens_d' | 'hedges_g' | 'glass_delta';
+
+// What a SimulationError reports; 'invalid_parameter' covers the remaining option checks
+export type SimulationErrorKind =
+  | 'invalid_std'
+  | 'empty_group'
+  | 'invalid_alpha'
+  | 'invalid_parameter'
+  | 'distribution_error'
+  | 'reproducibility_failure' // verify_reproducibility: two seeded runs disagreed
+  | 'cancelled'; // The run's abort signal fired
+
+// How runSimulationBatch seeds each parameter set (see there)
+export type BatchSeeding = 'shared' | 'per_index' | 'independent';
+
+// Correction applied when the simulated p-values are treated as one family of tests
+export type MultipleComparison = 'none' | 'bonferroni' | 'benjamini_hochberg';
+
+// Contaminated-normal outlier model: each draw, with probability `fraction`, has its deviation
+// from the group mean stretched by `scale`. The true effect still uses the uncontaminated std
+export interface Contamination {
+  fraction: number; // In [0, 1]
+  scale: number; // Positive; > 1 widens the contaminating component
+}
+
+export interface MixtureComponent {
+  weight: number;
+  mean: number;
+  std: number;
 }
 
+// Sampling distribution for one simulated group
+export type Distribution =
+  | { type: 'normal' }
+  | { type: 'mixture'; components: MixtureComponent[] } // Component means offset the group mean; the group std is unused
+  | { type: 'laplace' } // Peaked and heavy-tailed; scale std / sqrt(2) gives the group std
+  | { type: 'lognormal'; sigma: number } // Right-skewed; larger sigma, stronger skew
+  | { type: 'exponential' }
+  | { type: 'uniform' }
+  | { type: 'student_t'; df: number }; // Heavy-tailed; df > 2
+
+// How a single simulated result is judged significant (sesoi = smallest effect size of interest)
+export type SignificanceRule =
+  | { type: 'p_value' }
+  | { type: 'ci_excludes_null' }
+  | { type: 'p_value_and_sesoi'; sesoi: number }
+  | { type: 'ci_excludes_null_and_sesoi'; sesoi: number };
+
 export interface SimulationResult {
   p_value: number;
   effect_size: number;
   confidence_interval: [number, number];
   s_value: number;
   significant: boolean;
+  // Probability of superiority: P(random group 1 value > random group 2 value). Under glass_delta
+  // it maps Glass's delta, so it is only that probability when the group SDs are equal
+  cles: number;
+  cles_ci: [number, number]; // confidence_interval mapped through the CLES transform
+  t_statistic?: number; // t-test modes only
+  degrees_of_freedom?: number; // t-test modes only, after any min_df floor
+  bayes_factor?: number; // JZS BF10 (Cauchy prior, r = 0.707) from the pooled t; only with params.bayes_factor
+  hedges_g?: number; // Bias-corrected d (t-test modes only): effect_size * (1 - 3 / (4 df - 1))
+  noncentral_ci?: boolean; // CI switched to noncentral t because |d| exceeded the threshold
 }
 
 export interface AggregatedResults {
@@ -167,8 +309,148 @@ export interface AggregatedResults {
   total_count: number;
   mean_effect_size: number;
   effect_size_ci: [number, number];
+  effect_size_std: number; // Spread of the per-simulation effect sizes (same metric as mean_effect_size)
+  p_value_std: number;
+  mean_cles: number;
+  cles_ci: [number, number]; // Empirical 2.5/97.5 percentiles of the per-simulation cles
   ci_coverage: number;
+  false_coverage_rate: number; // Share of CIs excluding the true effect (≈ 1 - ci_confidence_level)
   mean_ci_width: number;
+  ci_width_histogram: ValueHistogramBin[]; // Per-simulation CI widths over their observed range
+  effect_by_pvalue_stratum: PValueStratum[];
+  // significant_count / total_count: empirical power under a real effect, and the empirical
+  // Type I error rate when the true effect is zero
+  power: number;
+  power_ci: [number, number]; // Clopper-Pearson 95% interval for power
+  power_wilson_ci: [number, number]; // Wilson score 95% interval for power
+  // Absent for Mann-Whitney unless effect_size_thresholds is given on the rank-biserial scale
+  effect_size_class_counts?: Record<EffectSizeClass, number>;
+  pi0_estimate: number; // Storey estimate of the share of true nulls among the p-values
+  uniformity_test: UniformityTest | null; // p_value_histogram against uniform; expected non-significant under the null. Null with one bin
+  // p < alpha count after the multiple_comparison correction over all simulated p-values;
+  // null for Benjamini-Hochberg without stored results, which needs every p-value ranked
+  adjusted_significant_count: number | null;
+  assurance?: number; // Bayesian expected power; only with an effect prior
+  relative_ci_halfwidth: number | null; // mean_ci_width / (2 |mean_effect_size|); null near zero effect
+  // Gelman-Carlin design analysis over significant results; null when no simulation is
+  // significant or the true effect is zero
+  type_m_error: number | null; // Exaggeration ratio: mean |d| / |true d|
+  type_s_error: number | null; // Share with the sign opposite to the true effect
+  // Wrong-sign significant results over all significant results; differs from type_s_error only
+  // when some significant results come from a zero true effect (e.g. null_fraction > 0). Null,
+  // like type_s_error, when no significant result has a nonzero true effect
+  wrong_direction_rate: number | null;
+  group1_std_histogram?: ValueHistogramBin[]; // Diagnostics only
+  group2_std_histogram?: ValueHistogramBin[]; // Diagnostics only
+  bootstrap_mc_error?: BootstrapMCError; // Diagnostics with a bootstrap ci_method only
+  endpoint_analysis?: EndpointAnalysis; // Only when num_endpoints > 1
+  percentile_summary?: PercentileSummary; // Only when percentile_summary is requested
+  sample_datasets?: SampleDataset[]; // Only when return_sample_datasets > 0
+  // Only when scatter_sample_size > 0: uniform random subset of (effect_size, p_value) points
+  scatter_sample?: Array<[number, number]>;
+  // Only when power_trace is set: (simulations completed, running power) for convergence plots
+  power_trace?: Array<[number, number]>;
+  null_rejection_rate?: number; // include_null_calibration only: Type I error rate at alpha_level
+  pooled_hedges_g?: PooledEffect; // Independent-groups t modes with n1 + n2 > 4
+  rope_decisions?: RopeDecisions; // Only when a ROPE is given
+  p_value_histogram_by_source?: SourceHistogramBin[]; // Only when null_fraction > 0
+  test_agreement?: TestAgreement; // Only when compare_tests is set
+  metadata: SimulationMetadata;
+}
+
+// Fixed-effect pool of Hedges' g across simulations. All simulations share n1 and n2, so the
+// weights are equal: the estimate is the mean g, se = sqrt(V(g at the mean) / count), 95% normal CI
+export interface PooledEffect {
+  estimate: number;
+  se: number;
+  ci: [number, number];
+}
+
+// Monte Carlo error of the bootstrap CI endpoints from a jackknife over each simulation's
+// bootstrap replicates, averaged across simulations
+export interface BootstrapMCError {
+  mean_lower_mc_error: number | null;
+  mean_upper_mc_error: number | null;
+}
+
+// HDI+ROPE decision rule applied to each simulation's effect-size CI
+export interface RopeDecisions {
+  rope: [number, number];
+  accept_rate: number; // CI entirely inside the ROPE
+  reject_rate: number; // CI entirely outside the ROPE
+  undecided_rate: number; // CI overlaps a ROPE boundary
+}
+
+export interface EndpointAnalysis {
+  num_endpoints: number;
+  endpoint_correlation: number;
+  adjusted_alpha: number; // Bonferroni: alpha_level / num_endpoints
+  per_endpoint_power: number[];
+  // Fraction of simulations with at least one significant endpoint; the FWER when no true effect exists
+  family_wise_error_rate: number;
+}
+
+// Raw samples behind one simulation
+export interface SampleDataset {
+  simulation_index: number;
+  group1: number[];
+  group2: number[];
+}
+
+export interface QuantileSummary {
+  min: number;
+  p5: number;
+  p25: number;
+  p50: number;
+  p75: number;
+  p95: number;
+  max: number;
+}
+
+// Compact distribution summary of the per-simulation quantities
+export interface PercentileSummary {
+  p_value: QuantileSummary;
+  effect_size: QuantileSummary;
+  s_value: QuantileSummary;
+  ci_width: QuantileSummary;
+}
+
+// Notes describing how the aggregates were produced
+export interface SimulationMetadata {
+  estimand: Estimand;
+  effect_size_metric: EffectSizeMetric;
+  multiple_comparison: MultipleComparison;
+  ci_confidence_level: number;
+  warnings: string[]; // Conditions that make the results less trustworthy
+  noncentral_ci_switches: number; // Simulations whose CI used the noncentral t
+  design_effect: number; // 1 + (cluster_size - 1) * icc
+  effective_sample_sizes: [number, number]; // n / design_effect per group
+  rng_draws?: number[]; // debug_rng only: first uniforms consumed, before any transform
+  size_calibration?: SizeCalibration; // self_calibrate only
+  timeout_cap?: TimeoutCap; // per_simulation_timeout_ms only
+  achievable_alpha?: number | null; // Permutation test only: largest attainable level <= alpha_level
+  // Antithetic only: plain Monte Carlo binomial variance of power over the observed (batch) variance;
+  // > 1 means fewer simulations were needed for the same precision
+  variance_reduction_factor?: number | null;
+}
+
+// How often per_simulation_timeout_ms cut resampling short
+export interface TimeoutCap {
+  per_simulation_timeout_ms: number;
+  capped_simulations: number; // Bootstrap CIs cut short
+  mean_capped_resamples: number | null; // Average resamples drawn by the capped simulations
+  capped_permutation_simulations: number; // Permutation tests cut short
+  mean_capped_permutations: number | null; // Average permutations drawn by those simulations
+}
+
+// Preliminary null run behind self_calibrate
+export interface SizeCalibration {
+  nominal_alpha: number;
+  estimated_size: number; // Share of null p-values below nominal_alpha
+  calibration_simulations: number;
+  // Null p-value quantile at nominal_alpha; when set, significance used this threshold,
+  // so the reported power is size-adjusted
+  calibrated_alpha: number | null;
 }
 
 export interface HistogramBin {
@@ -178,6 +460,43 @@ export interface HistogramBin {
   significant: boolean;
 }
 
+// Chi-square test of a p-value histogram against uniformity
+export interface UniformityTest {
+  statistic: number;
+  df: number;
+  p_value: number;
+}
+
+// Incremental feed from a streamed simulation run
+export type SimulationEvent =
+  | { type: 'results'; results: SimulationResult[] } // Batched as they are produced
+  | { type: 'progress'; completed: number; total: number } // About every 1% of simulations
+  | { type: 'done'; aggregated: AggregatedResults };
+
+// p-value bin split by whether each simulation ran under the null or the alternative
+export interface SourceHistogramBin {
+  bin_start: number;
+  bin_end: number;
+  null_count: number;
+  alt_count: number;
+}
+
+// Mean observed effect among simulations whose p-value falls in [p_low, p_high)
+export interface PValueStratum {
+  p_low: number;
+  p_high: number;
+  mean_effect: number | null; // null for empty strata
+  mean_abs_effect: number | null;
+  count: number;
+}
+
+// Generic histogram bin over an observed value range
+export interface ValueHistogramBin {
+  bin_start: number;
+  bin_end: number;
+  count: number;
+}
+
 export interface AggregatedStats {
   significant_count: number;
   total_count: number;
@@ -208,7 +527,109 @@ export interface ThresholdSensitivity {
 export type ThemeType = 'light' | 'dark' | 'auto';
 export type EffectSizeCategory = 'negligible' | 'small' | 'medium' | 'large';
 export type SignificanceLevel = 0.001 | 0.01 | 0.05 | 0.10;
-export type TestType = 'welch' | 'pooled' | 'mann_whitney';
+export type TestType = 'welch' | 'pooled' | 'mann_whitney' | 'paired' | 'permutation';
+
+// One point of a power-vs-sample-size curve
+export interface SampleSizePoint {
+  n: number; // Per-group sample size
+  power: number;
+  power_ci: [number, number]; // Clopper-Pearson 95%
+  mean_effect_size: number;
+}
+
+// One cell of a power heatmap
+export interface PowerGridCell {
+  n: number; // Per-group sample size
+  effect: number; // True standardized effect (Cohen's d)
+  power: number;
+}
+
+export interface AllocationPoint {
+  allocation_fraction: number; // Share of total N in group 1
+  n1: number;
+  n2: number;
+  power: number;
+}
+
+export interface DFCalibration {
+  nominal_alpha: number;
+  forced_df: Array<{ df: number; type1_error_rate: number }>;
+  welch_type1_error_rate: number; // Using each sample's Satterthwaite df
+}
+
+// One point of a PP (calibration) plot; a calibrated test lies on the diagonal
+export interface CalibrationPoint {
+  nominal_alpha: number;
+  empirical_alpha: number; // Share of null p-values below nominal_alpha
+}
+
+export interface CIWidthPoint {
+  n: number; // Per-group sample size
+  expected_width: number; // Analytic width of the effect-size CI
+}
+
+export interface AlphaPowerPoint {
+  alpha: number;
+  power: number; // Share of stored p-values below alpha
+}
+
+// Paired t-test on supplied before/after measurements
+export interface PairedAnalysis {
+  n: number;
+  t_statistic: number;
+  df: number;
+  p_value: number;
+  correlation: number; // Pearson r between before and after; NaN if either is constant
+  dz: number; // Cohen's d_z = mean(after - before) / sd(after - before)
+  dz_ci: [number, number];
+}
+
+export interface ReplicationResults {
+  replication_power: number; // Probability a replication reaches significance
+  mean_effect_size: number;
+  effect_size_interval: [number, number]; // 2.5th / 97.5th percentiles across replications
+  effect_size_histogram: ValueHistogramBin[];
+  effect_sizes: number[];
+}
+
+export interface PosteriorEffectSize {
+  observed_effect_size: number;
+  observed_se: number;
+  posterior_mean: number;
+  posterior_sd: number;
+  credible_interval: [number, number]; // 95% central interval
+}
+
+export interface TestOutcome {
+  p_value: number;
+  effect_size: number;
+  significant: boolean;
+}
+
+export interface TestComparison {
+  t_test: TestOutcome; // effect_size is Cohen's d
+  mann_whitney: TestOutcome; // effect_size is the rank-biserial correlation
+  decisions_agree: boolean;
+}
+
+// t-test vs Mann-Whitney on identical simulated datasets
+export interface TestAgreement {
+  mean_p_value_difference: number; // Mean of (t-test p - Mann-Whitney p)
+  p_value_difference_histogram: ValueHistogramBin[];
+  disagreement_rate: number; // Share of datasets where exactly one test is significant at alpha_level
+}
+
+export type StatisticKind = 'p_value' | 'z_score' | 's_value';
+
+export type EffectSizeClass = 'negligible' | 'small' | 'medium' | 'large';
+
+// Rules for combining independent p-values into one
+export type CombineMethod = 'fisher' | 'stouffer' | 'harmonic_mean';
+
+export interface TestRecommendation {
+  recommended: TestType;
+  reasons: string[];
+}
 // Simulation Studies - Enhanced analytical units
 export interface SimulationStudy {
   id: string;
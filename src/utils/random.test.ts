@@ -0,0 +1,55 @@
+import { describe, it, expect } from 'vitest';
+import { createIndexedRandom, createSeededRandom, deriveSeed, recordDraws } from './random';
+
+describe('recordDraws', () => {
+  it('captures the first uniforms without changing the stream', () => {
+    const plain = createSeededRandom(222);
+    const recorded = recordDraws(createSeededRandom(222), 3);
+
+    const values = Array.from({length: 5}, () => recorded.random());
+    expect(values).toEqual(Array.from({length: 5}, () => plain()));
+    expect(recorded.draws).toEqual(values.slice(0, 3));
+  });
+});
+
+describe('deriveSeed', () => {
+  it('is deterministic and separates indices and streams', () => {
+    expect(deriveSeed(42, 7, 1)).toBe(deriveSeed(42, 7, 1));
+    const seeds = new Set<number>();
+    for (let index = 0; index < 1000; index++) {
+      for (let stream = 0; stream < 3; stream++) seeds.add(deriveSeed(42, index, stream));
+    }
+    expect(seeds.size).toBe(3000);
+  });
+
+  it('separates seeds beyond 32 bits', () => {
+    expect(deriveSeed(2 ** 32 + 5, 0)).not.toBe(deriveSeed(5, 0));
+  });
+});
+
+describe('createIndexedRandom', () => {
+  it('gives each index the same draws whatever order the indices run in', () => {
+    const firstDraws = (order: number[]) => {
+      const draws: number[] = [];
+      order.forEach(index => { draws[index] = createIndexedRandom(9, index)(); });
+      return draws;
+    };
+    const forward = Array.from({length: 50}, (_, i) => i);
+    expect(firstDraws([...forward].reverse())).toEqual(firstDraws(forward));
+  });
+
+  it('produces uniform, uncorrelated streams across neighbouring indices', () => {
+    const count = 4000;
+    const u = Array.from({length: count + 1}, (_, index) => createIndexedRandom(9, index)());
+    const mean = u.reduce((sum, v) => sum + v, 0) / u.length;
+    // Uniform mean has SD 1 / sqrt(12 n); lag-1 correlation has SD about 1 / sqrt(n)
+    expect(Math.abs(mean - 0.5)).toBeLessThan(4 / Math.sqrt(12 * u.length));
+    let covariance = 0;
+    for (let i = 0; i < count; i++) covariance += (u[i] - 0.5) * (u[i + 1] - 0.5);
+    expect(Math.abs(covariance / count * 12)).toBeLessThan(4 / Math.sqrt(count));
+  });
+
+  it('falls back to Math.random without a seed', () => {
+    expect(createIndexedRandom(undefined, 3)).toBe(Math.random);
+  });
+});
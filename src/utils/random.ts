@@ -0,0 +1,62 @@
+// Seedable random number generation for reproducible simulations
+
+// Uniform [0, 1) generator, interchangeable with Math.random
+export type RandomSource = () => number;
+
+// Fold seeds beyond 32 bits into the generator state so large seeds stay distinct
+const hashSeed = (seed: number): number => {
+  const low = seed >>> 0;
+  const high = Math.floor(Math.abs(seed) / 4294967296) >>> 0;
+  let h = Math.imul(low ^ 0x9E3779B9, 0x85EBCA6B) ^ Math.imul(high, 0xC2B2AE35);
+  h ^= h >>> 16;
+  return h >>> 0;
+};
+
+// Mulberry32: small and fast 32-bit generator, adequate for Monte Carlo work
+export const createSeededRandom = (seed: number): RandomSource => {
+  let state = hashSeed(seed);
+
+  return () => {
+    state = (state + 0x6D2B79F5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+};
+
+// Counter-based seed derivation: mixes (seed, index, stream) into an independent 32-bit seed,
+// so the draws of simulation i depend only on the base seed and i, never on execution order
+export const deriveSeed = (seed: number, index: number, stream: number = 0): number => {
+  let h = hashSeed(seed) ^ Math.imul(index + 1, 0x9E3779B1) ^ Math.imul(stream + 1, 0x85EBCA77);
+  h = Math.imul(h ^ (h >>> 16), 0x7FEB352D);
+  h = Math.imul(h ^ (h >>> 15), 0x846CA68B);
+  return (h ^ (h >>> 16)) >>> 0;
+};
+
+// Generator for one simulation index; non-deterministic when no base seed is given
+export const createIndexedRandom = (seed: number | undefined, index: number, stream: number = 0): RandomSource => {
+  return seed === undefined ? Math.random : createSeededRandom(deriveSeed(seed, index, stream));
+};
+
+// Seeded generator when a seed is given, otherwise the non-deterministic Math.random
+export const createRandom = (seed?: number): RandomSource => {
+  return seed === undefined ? Math.random : createSeededRandom(seed);
+};
+
+// Wrap a source so the first `limit` uniforms it produces are captured for auditing
+export const recordDraws = (
+  random: RandomSource,
+  limit: number,
+  draws: number[] = []
+): { random: RandomSource; draws: number[] } => {
+
+  return {
+    random: () => {
+      const u = random();
+      if (draws.length < limit) draws.push(u);
+      return u;
+    },
+    draws
+  };
+};
@@ -0,0 +1,163 @@
+import { describe, it, expect } from 'vitest';
+import { appendToCSVFile, exportToCSVTidy, exportToCSVWithScenario, exportToJSON, exportToNDJSON, writeCSVToFile } from './resultsExport';
+import { runStatisticalSimulation } from '../services/multi-pair-simulation';
+import { AggregatedResults, SimulationParams } from '../types/simulation.types';
+
+const params = (overrides: Partial<SimulationParams> = {}): SimulationParams => ({
+  group1_mean: 0.5,
+  group1_std: 1,
+  group2_mean: 0,
+  group2_std: 1,
+  sample_size_per_group: 20,
+  num_simulations: 3,
+  hypothesized_effect_size: 0.5,
+  alpha_level: 0.05,
+  seed: 12345,
+  ...overrides
+});
+
+// In-memory stand-in for a File System Access handle: writes land in a swap copy that
+// replaces the file's contents on close, as with a real writable stream
+const memoryFile = (initial: string = '') => {
+  const file = { contents: initial, writes: 0 };
+  const handle = {
+    getFile: async () => ({ size: file.contents.length }),
+    createWritable: async (options: { keepExistingData?: boolean } = {}) => {
+      let swap = options.keepExistingData ? file.contents : '';
+      let position = 0;
+      return {
+        seek: async (offset: number) => { position = offset; },
+        write: async (chunk: string) => {
+          swap = swap.slice(0, position) + chunk + swap.slice(position + chunk.length);
+          position += chunk.length;
+          file.writes++;
+        },
+        close: async () => { file.contents = swap; }
+      };
+    }
+  };
+  return { file, handle: handle as unknown as FileSystemFileHandle };
+};
+
+describe('exportToCSVTidy', () => {
+  it('writes one row per simulation and metric', async () => {
+    const results = await runStatisticalSimulation(params());
+    const lines = exportToCSVTidy(results).trimEnd().split('\n');
+
+    expect(lines[0]).toBe('simulation_id,metric,value');
+    expect(lines).toHaveLength(1 + 3 * 7);
+    expect(lines[1]).toBe(`1,p_value,${results.individual_results[0].p_value.toFixed(6)}`);
+    expect(lines.slice(15).map(line => line.split(',')[1])).toEqual([
+      'p_value', 'effect_size', 's_value', 'ci_lower', 'ci_upper', 't_statistic', 'degrees_of_freedom'
+    ]);
+    expect(lines[21]).toBe('3,degrees_of_freedom,38.000000');
+  });
+
+  it('leaves out the t-test metrics for Mann-Whitney runs', async () => {
+    const results = await runStatisticalSimulation(params({ test_type: 'mann_whitney' }));
+    const lines = exportToCSVTidy(results).trimEnd().split('\n');
+    expect(lines).toHaveLength(1 + 3 * 5);
+    expect(lines.some(line => line.includes('t_statistic'))).toBe(false);
+  });
+
+  it('refuses runs without per-simulation rows', async () => {
+    const results = await runStatisticalSimulation(params({ store_individual_results: false }));
+    expect(() => exportToCSVTidy(results)).toThrow('per-simulation rows');
+  });
+});
+
+describe('exportToJSON', () => {
+  it('round-trips the rows, histograms and aggregates', async () => {
+    const results = await runStatisticalSimulation(params({ num_simulations: 20 }));
+    const parsed: AggregatedResults = JSON.parse(exportToJSON(results));
+
+    expect(parsed.individual_results).toEqual(results.individual_results);
+    expect(parsed.p_value_histogram).toEqual(results.p_value_histogram);
+    expect(parsed.power).toBe(results.power);
+    expect(parsed.effect_size_ci).toEqual(results.effect_size_ci);
+    expect(Object.keys(parsed).sort())
+      .toEqual(Object.keys(results).filter(key => (results as any)[key] !== undefined).sort());
+  });
+
+  it('writes tuples as two-element arrays and non-finite numbers as null', async () => {
+    const results = await runStatisticalSimulation(params());
+    results.individual_results[0].s_value = Infinity;
+    const parsed = JSON.parse(exportToJSON(results));
+
+    expect(parsed.individual_results[0].confidence_interval).toEqual(results.individual_results[0].confidence_interval);
+    expect(parsed.individual_results[0].confidence_interval).toHaveLength(2);
+    expect(parsed.individual_results[0].s_value).toBeNull();
+  });
+});
+
+describe('exportToNDJSON', () => {
+  it('writes a simulation line per result and a trailing summary line', async () => {
+    const results = await runStatisticalSimulation(params());
+    const lines = exportToNDJSON(results).trimEnd().split('\n').map(line => JSON.parse(line));
+
+    expect(lines).toHaveLength(4);
+    expect(lines.slice(0, 3).map(line => line.simulation_id)).toEqual([1, 2, 3]);
+    expect(lines[0]).toMatchObject({
+      type: 'simulation',
+      p_value: results.individual_results[0].p_value,
+      ci_lower: results.individual_results[0].confidence_interval[0],
+      degrees_of_freedom: 38
+    });
+    expect(lines[3]).toMatchObject({ type: 'summary', total_count: 3, power: results.power });
+  });
+
+  it('writes missing t-test fields and infinite S-values as null', async () => {
+    const results = await runStatisticalSimulation(params({ test_type: 'mann_whitney' }));
+    results.individual_results[0].s_value = Infinity;
+    const first = JSON.parse(exportToNDJSON(results).split('\n')[0]);
+    expect(first.t_statistic).toBeNull();
+    expect(first.s_value).toBeNull();
+  });
+});
+
+describe('exportToCSVWithScenario', () => {
+  it('tags every row with the quoted scenario id', async () => {
+    const results = await runStatisticalSimulation(params());
+    const lines = exportToCSVWithScenario(results, 'n=20, d="0.5"').trimEnd().split('\n');
+
+    expect(lines[0]).toBe('scenario_id,simulation_id,p_value,effect_size,ci_lower,ci_upper,s_value,' +
+      't_statistic,degrees_of_freedom,significant');
+    expect(lines).toHaveLength(4);
+    expect(lines[1].startsWith('"n=20, d=""0.5""",1,')).toBe(true);
+    expect(exportToCSVWithScenario(results, 'a', false).split('\n')[0].startsWith('a,1,')).toBe(true);
+  });
+});
+
+describe('writeCSVToFile', () => {
+  it('replaces the file with the header and every row, written in chunks', async () => {
+    const results = await runStatisticalSimulation(params({ num_simulations: 2500 }));
+    const { file, handle } = memoryFile('stale contents\n');
+
+    await writeCSVToFile(results, 'run', handle);
+
+    expect(file.contents).toBe(exportToCSVWithScenario(results, 'run'));
+    const lines = file.contents.trimEnd().split('\n');
+    expect(lines[0].startsWith('scenario_id,simulation_id,')).toBe(true);
+    expect(lines).toHaveLength(2501);
+    // 1000 + 1000 + 501 lines
+    expect(file.writes).toBe(3);
+  });
+
+  it('refuses runs without per-simulation rows', async () => {
+    const results = await runStatisticalSimulation(params({ store_individual_results: false }));
+    await expect(writeCSVToFile(results, 'run', memoryFile().handle)).rejects.toThrow('per-simulation rows');
+  });
+});
+
+describe('appendToCSVFile', () => {
+  it('writes the header only into an empty file and appends later runs below it', async () => {
+    const first = await runStatisticalSimulation(params());
+    const second = await runStatisticalSimulation(params({ seed: 54321 }));
+    const { file, handle } = memoryFile();
+
+    await appendToCSVFile(first, 'first', handle);
+    await appendToCSVFile(second, 'second', handle);
+
+    expect(file.contents).toBe(exportToCSVWithScenario(first, 'first') + exportToCSVWithScenario(second, 'second', false));
+  });
+});
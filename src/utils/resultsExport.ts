@@ -0,0 +1,199 @@
+import { AggregatedResults } from '../types/simulation.types';
+
+// Text exports of per-simulation results for analysis outside the app
+
+const TIDY_METRICS = [
+  'p_value', 'effect_size', 's_value', 'ci_lower', 'ci_upper', 't_statistic', 'degrees_of_freedom'
+] as const;
+
+// CSV exports are per-simulation rows; runs that kept only aggregates have none to write
+const requireIndividualResults = (results: AggregatedResults): void => {
+  if (results.individual_results.length === 0) {
+    throw new Error('These results hold no per-simulation rows to export; ' +
+      'run with store_individual_results enabled and without percentile_summary');
+  }
+};
+
+// Long-format CSV: one row per (simulation, metric) pair, ready for tidyverse / pandas melt-style workflows.
+// Metrics a test does not produce (t statistic and df outside the t-test modes) have no row
+export const exportToCSVTidy = (results: AggregatedResults): string => {
+  requireIndividualResults(results);
+  const lines = ['simulation_id,metric,value'];
+
+  results.individual_results.forEach((result, index) => {
+    const values: Record<typeof TIDY_METRICS[number], number | undefined> = {
+      p_value: result.p_value,
+      effect_size: result.effect_size,
+      s_value: result.s_value,
+      ci_lower: result.confidence_interval[0],
+      ci_upper: result.confidence_interval[1],
+      t_statistic: result.t_statistic,
+      degrees_of_freedom: result.degrees_of_freedom
+    };
+
+    TIDY_METRICS.forEach(metric => {
+      const value = values[metric];
+      if (value !== undefined) lines.push(`${index + 1},${metric},${value.toFixed(6)}`);
+    });
+  });
+
+  return lines.join('\n') + '\n';
+};
+
+const SCENARIO_CSV_HEADER =
+  'scenario_id,simulation_id,p_value,effect_size,ci_lower,ci_upper,s_value,t_statistic,degrees_of_freedom,significant';
+
+// Quote a free-text CSV field when it contains a delimiter, quote or line break
+const csvField = (value: string): string => {
+  return /[",\r\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+};
+
+// Wide CSV lines (newline-terminated) tagged with a scenario id, so several runs can share one file
+function* scenarioCSVLines(
+  results: AggregatedResults,
+  scenario_id: string,
+  include_header: boolean
+): Generator<string> {
+  const scenario = csvField(scenario_id);
+  if (include_header) yield SCENARIO_CSV_HEADER + '\n';
+
+  for (let index = 0; index < results.individual_results.length; index++) {
+    const result = results.individual_results[index];
+    yield [
+      scenario,
+      index + 1,
+      result.p_value.toFixed(6),
+      result.effect_size.toFixed(6),
+      result.confidence_interval[0].toFixed(6),
+      result.confidence_interval[1].toFixed(6),
+      result.s_value.toFixed(6),
+      result.t_statistic?.toFixed(6) ?? '',
+      result.degrees_of_freedom?.toFixed(6) ?? '',
+      result.significant ? 'TRUE' : 'FALSE'
+    ].join(',') + '\n';
+  }
+}
+
+export const exportToCSVWithScenario = (
+  results: AggregatedResults,
+  scenario_id: string,
+  include_header: boolean = true
+): string => {
+  requireIndividualResults(results);
+  return Array.from(scenarioCSVLines(results, scenario_id, include_header)).join('');
+};
+
+// Lines handed to the file writer per write call
+const FILE_WRITE_CHUNK_LINES = 1000;
+
+// Stream lines into an open writable in fixed-size chunks instead of one giant string
+const writeLines = async (writable: FileSystemWritableFileStream, lines: Iterable<string>): Promise<void> => {
+  let chunk: string[] = [];
+  for (const line of lines) {
+    chunk.push(line);
+    if (chunk.length >= FILE_WRITE_CHUNK_LINES) {
+      await writable.write(chunk.join(''));
+      chunk = [];
+    }
+  }
+  if (chunk.length > 0) await writable.write(chunk.join(''));
+};
+
+// Write a run straight to a user-picked file (File System Access API), replacing its contents
+export const writeCSVToFile = async (
+  results: AggregatedResults,
+  scenario_id: string,
+  handle: FileSystemFileHandle
+): Promise<void> => {
+  requireIndividualResults(results);
+  const writable = await handle.createWritable();
+
+  try {
+    await writeLines(writable, scenarioCSVLines(results, scenario_id, true));
+  } finally {
+    await writable.close();
+  }
+};
+
+// Append a run to a user-picked CSV file (File System Access API). The header is written only
+// when the file is empty, so repeated calls build one combined dataset across scenarios
+export const appendToCSVFile = async (
+  results: AggregatedResults,
+  scenario_id: string,
+  handle: FileSystemFileHandle
+): Promise<void> => {
+  requireIndividualResults(results);
+  const existing_size = (await handle.getFile()).size;
+  const writable = await handle.createWritable({ keepExistingData: true });
+
+  try {
+    await writable.seek(existing_size);
+    await writeLines(writable, scenarioCSVLines(results, scenario_id, existing_size === 0));
+  } finally {
+    await writable.close();
+  }
+};
+
+// The full aggregated results (rows, histograms, aggregates, metadata) as pretty-printed JSON.
+// Tuples such as confidence_interval become two-element arrays; non-finite numbers become null
+export const exportToJSON = (results: AggregatedResults): string => {
+  return JSON.stringify(results, null, 2);
+};
+
+export const downloadJSON = (results: AggregatedResults, filename: string): void => {
+  downloadTextFile(exportToJSON(results), filename, 'json', 'application/json');
+};
+
+// Newline-delimited JSON, one line at a time: a `simulation` object per result, then a `summary` line.
+// Non-finite numbers (e.g. an infinite S-value) serialize as null, as with JSON.stringify
+export function* ndjsonLines(results: AggregatedResults): Generator<string> {
+  for (let index = 0; index < results.individual_results.length; index++) {
+    const result = results.individual_results[index];
+    yield JSON.stringify({
+      type: 'simulation',
+      simulation_id: index + 1,
+      p_value: result.p_value,
+      effect_size: result.effect_size,
+      ci_lower: result.confidence_interval[0],
+      ci_upper: result.confidence_interval[1],
+      s_value: result.s_value,
+      t_statistic: result.t_statistic ?? null,
+      degrees_of_freedom: result.degrees_of_freedom ?? null,
+      significant: result.significant
+    }) + '\n';
+  }
+
+  yield JSON.stringify({
+    type: 'summary',
+    total_count: results.total_count,
+    significant_count: results.significant_count,
+    power: results.power,
+    mean_effect_size: results.mean_effect_size,
+    effect_size_ci: results.effect_size_ci,
+    ci_coverage: results.ci_coverage,
+    mean_ci_width: results.mean_ci_width
+  }) + '\n';
+}
+
+export const exportToNDJSON = (results: AggregatedResults): string => {
+  return Array.from(ndjsonLines(results)).join('');
+};
+
+// Download NDJSON without first concatenating every line into one string
+export const downloadNDJSON = (results: AggregatedResults, filename: string): void => {
+  downloadTextFile(Array.from(ndjsonLines(results)), filename, 'ndjson', 'application/x-ndjson');
+};
+
+// Trigger a browser download of text content with a timestamped filename
+export const downloadTextFile = (content: string | string[], filename: string, extension: string, mimeType: string): void => {
+  const blob = new Blob(Array.isArray(content) ? content : [content], { type: mimeType });
+  const url = window.URL.createObjectURL(blob);
+  const a = document.createElement('a');
+  a.href = url;
+  const timestamp = new Date().toISOString().slice(0, 19).replace(/:/g, '-');
+  a.download = `${filename}_${timestamp}.${extension}`;
+  document.body.appendChild(a);
+  a.click();
+  document.body.removeChild(a);
+  window.URL.revokeObjectURL(url);
+};
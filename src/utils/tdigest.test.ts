@@ -0,0 +1,44 @@
+import { describe, it, expect } from 'vitest';
+import { TDigest } from './tdigest';
+import { createSeededRandom } from './random';
+
+describe('TDigest', () => {
+  it('tracks the quantiles of a large stream within a small error', () => {
+    const random = createSeededRandom(211);
+    const digest = new TDigest();
+    const values: number[] = [];
+    for (let i = 0; i < 100000; i++) {
+      const value = random();
+      values.push(value);
+      digest.add(value);
+    }
+    values.sort((a, b) => a - b);
+
+    expect(digest.count).toBe(100000);
+    expect(digest.quantile(0)).toBe(values[0]);
+    expect(digest.quantile(1)).toBe(values[values.length - 1]);
+    for (const q of [0.001, 0.025, 0.25, 0.5, 0.75, 0.975, 0.999]) {
+      expect(Math.abs(digest.quantile(q) - values[Math.floor(q * values.length)])).toBeLessThan(0.005);
+    }
+  });
+
+  it('estimates the CDF as the inverse of its quantiles', () => {
+    const random = createSeededRandom(270);
+    const digest = new TDigest();
+    for (let i = 0; i < 100000; i++) digest.add(random());
+
+    expect(digest.cdf(-1)).toBe(0);
+    expect(digest.cdf(digest.quantile(1))).toBe(1);
+    for (const x of [0.01, 0.1, 0.5, 0.9, 0.99]) {
+      expect(Math.abs(digest.cdf(x) - x)).toBeLessThan(0.005);
+    }
+    for (const q of [0.05, 0.5, 0.95]) {
+      expect(digest.cdf(digest.quantile(q))).toBeCloseTo(q, 6);
+    }
+  });
+
+  it('has no quantiles before any value is added', () => {
+    expect(new TDigest().quantile(0.5)).toBeNaN();
+    expect(new TDigest().cdf(0.5)).toBeNaN();
+  });
+});
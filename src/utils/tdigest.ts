@@ -0,0 +1,120 @@
+// Streaming quantile estimation with bounded memory (merging t-digest, Dunning & Ertl)
+
+interface Centroid {
+  mean: number;
+  count: number;
+}
+
+export class TDigest {
+  private centroids: Centroid[] = [];
+  private buffer: number[] = [];
+  private total = 0;
+  private min = Infinity;
+  private max = -Infinity;
+
+  // Higher compression keeps more centroids: better accuracy, more memory
+  constructor(private readonly compression: number = 200, private readonly bufferSize: number = 1000) {}
+
+  add(value: number): void {
+    this.buffer.push(value);
+    this.min = Math.min(this.min, value);
+    this.max = Math.max(this.max, value);
+    if (this.buffer.length >= this.bufferSize) {
+      this.compress();
+    }
+  }
+
+  get count(): number {
+    return this.total + this.buffer.length;
+  }
+
+  // Estimate the q-th quantile (0 <= q <= 1) by interpolating between centroid centres
+  quantile(q: number): number {
+    this.compress();
+    if (this.total === 0) return NaN;
+    if (q <= 0) return this.min;
+    if (q >= 1) return this.max;
+
+    const target = q * this.total;
+    let cumulative = 0;
+    let prev_center = 0;
+    let prev_mean = this.min;
+
+    for (const c of this.centroids) {
+      const center = cumulative + c.count / 2;
+      if (target < center) {
+        const fraction = (target - prev_center) / (center - prev_center);
+        return prev_mean + fraction * (c.mean - prev_mean);
+      }
+      cumulative += c.count;
+      prev_center = center;
+      prev_mean = c.mean;
+    }
+
+    const fraction = (target - prev_center) / (this.total - prev_center);
+    return prev_mean + fraction * (this.max - prev_mean);
+  }
+
+  // Estimated share of values <= x, from the same piecewise-linear curve quantile() inverts
+  cdf(x: number): number {
+    this.compress();
+    if (this.total === 0) return NaN;
+    if (x < this.min) return 0;
+    if (x >= this.max) return 1;
+
+    let cumulative = 0;
+    let prev_center = 0;
+    let prev_mean = this.min;
+    const points = this.centroids.map(c => {
+      const center = cumulative + c.count / 2;
+      cumulative += c.count;
+      return { center, mean: c.mean };
+    });
+    points.push({ center: this.total, mean: this.max });
+
+    for (const { center, mean } of points) {
+      if (x < mean) {
+        const fraction = (x - prev_mean) / (mean - prev_mean);
+        return (prev_center + fraction * (center - prev_center)) / this.total;
+      }
+      prev_center = center;
+      prev_mean = mean;
+    }
+    return 1;
+  }
+
+  // Merge buffered values into the centroid list under the q(1-q) size bound,
+  // which keeps tail centroids small so extreme quantiles stay accurate
+  private compress(): void {
+    if (this.buffer.length === 0) return;
+
+    const incoming = this.buffer.map(value => ({ mean: value, count: 1 }));
+    const all = this.centroids.concat(incoming).sort((a, b) => a.mean - b.mean);
+    this.total += this.buffer.length;
+    this.buffer = [];
+
+    const merged: Centroid[] = [];
+    let current = { ...all[0] };
+    let so_far = 0;
+
+    for (let i = 1; i < all.length; i++) {
+      const next = all[i];
+      const proposed = current.count + next.count;
+      const q0 = so_far / this.total;
+      const q2 = (so_far + proposed) / this.total;
+      const limit = 4 * this.total * Math.min(q0 * (1 - q0), q2 * (1 - q2)) / this.compression;
+
+      if (proposed <= limit) {
+        current.mean += (next.mean - current.mean) * next.count / proposed;
+        current.count = proposed;
+      } else {
+        so_far += current.count;
+        merged.push(current);
+        current = { ...next };
+      }
+    }
+    merged.push(current);
+
+    this.centroids = merged;
+  }
+}
@@ -15,6 +15,7 @@ export const SamplePairSchema = z.object({
   group1: PopulationParamsSchema,
   group2: PopulationParamsSchema,
   sample_size_per_group: z.number().int().min(2).max(10000),
+  group2_sample_size: z.number().int().min(2).max(10000).optional(),
   enabled: z.boolean().default(true),
   color_scheme: z.string().optional(),
 });
@@ -24,7 +25,7 @@ export const GlobalSimulationSettingsSchema = z.object({
   significance_levels: z.array(z.number().min(0).max(1)).min(1).max(5),
   confidence_level: z.number().min(0.8).max(0.999),
   random_seed: z.number().int().optional(),
-  test_type: z.enum(['welch', 'pooled', 'mann_whitney']),
+  test_type: z.enum(['welch', 'pooled', 'mann_whitney', 'paired', 'permutation']),
 });
 
 export const UIPreferencesSchema = z.object({
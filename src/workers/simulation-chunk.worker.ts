@@ -0,0 +1,28 @@
+// Parallel simulation worker
+// Simulates one contiguous range of indices, for the main thread to fold in order, or one whole
+// run (a power grid cell)
+
+import { IterationSetup, SimulationError, runStatisticalSimulation, simulateRange } from '../services/multi-pair-simulation';
+import { SimulationParams } from '../types/simulation.types';
+
+type ChunkWorkerMessage =
+  | { type: 'RANGE'; setup: IterationSetup; start: number; end: number }
+  | { type: 'RUN'; params: SimulationParams };
+
+self.onmessage = async (e: MessageEvent<ChunkWorkerMessage>) => {
+  try {
+    if (e.data.type === 'RUN') {
+      self.postMessage({ type: 'SUCCESS', results: await runStatisticalSimulation(e.data.params) });
+    } else {
+      const { setup, start, end } = e.data;
+      self.postMessage({ type: 'SUCCESS', records: simulateRange(setup, start, end) });
+    }
+  } catch (error) {
+    // The kind survives the thread boundary so the main thread can rethrow a SimulationError
+    self.postMessage({
+      type: 'ERROR',
+      error: error instanceof Error ? error.message : 'Unknown error occurred',
+      kind: error instanceof SimulationError ? error.kind : undefined
+    });
+  }
+};
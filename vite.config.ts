@@ -1,3 +1,4 @@
+/// <reference types="vitest/config" />
 import { defineConfig } from 'vite'
 import react from '@vitejs/plugin-react'
 
@@ -10,5 +11,12 @@ export default defineConfig({
   build: {
     outDir: 'dist'
   },
-  root: '.'
+  root: '.',
+  test: {
+    environment: 'node',
+    include: ['src/**/*.test.ts'],
+    benchmark: {
+      include: ['src/**/*.bench.ts']
+    }
+  }
 })
\ No newline at end of file